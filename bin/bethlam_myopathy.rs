@@ -7,7 +7,7 @@ use phenopackets::schema::v2::{core::{
 use phenopackets::schema::v2::core::time_element::Element;
 use std::{collections::HashMap, env::var};
 
-use phenopacket_tools::{builders::{builder::Builder, expressions::Expressions, resources::{self, Resources}, time_elements}, constants::{self, allelic_state::AllelicState}};
+use phenopacket_tools::{builders::{builder::Builder, expressions::Expressions, phenopacket_builder::PhenopacketBuilder, resources::{self, Resources}, time_elements}, constants::{self, allelic_state::AllelicState}};
 use phenopacket_tools::error::{self, Error, Result};
 
 
@@ -20,24 +20,14 @@ pub fn bethlem_myopathy_phenopacket() -> Result<Phenopacket> {
             label: "author statement supported by traceable reference".to_string(),
         }),
         reference: Some(external_reference.clone()),
-        ..Default::default()
     };
 
     let mut individual = Builder::individual("proband A");
     individual.set_sex(Sex::Male);
     individual.time_at_last_encounter = Some(time_elements::age("P6Y3M")?);
-    let geno = resources::Resources::geno_version("2020-03-08");
-    let eco = Resources::eco_version("2022-08-05");
-    let omim = Resources::omim_version("2022-11-23");
-    let hpo_r = Resources::hpo_version("2021-08-02");
-    
 
     let created_ts = time_elements::timestamp_from_str("2021-05-14T10:35:00Z")?;
     let mut metadata = Builder::meta_data(created_ts, "anonymous biocurator");
-    metadata.resources.push(hpo_r);
-    metadata.resources.push(geno);
-    metadata.resources.push(eco);
-    metadata.resources.push(omim);
     metadata.external_references.push(external_reference);
    
 
@@ -66,7 +56,7 @@ pub fn bethlem_myopathy_phenopacket() -> Result<Phenopacket> {
  
 
     // Create PhenotypicFeatures
-    let phenotypic_features = vec![
+    let phenotypic_features: Vec<PhenotypicFeature> = vec![
         ("HP:0001629", "Ventricular septal defect", Some("congenital")),
         ("HP:0000280", "Coarse facial features", None),
         ("HP:0008689", "Bilateral cryptorchidism", Some("congenital")),
@@ -105,14 +95,16 @@ pub fn bethlem_myopathy_phenopacket() -> Result<Phenopacket> {
     .collect();
 
     // Create Phenopacket
-    let phenopacket = Phenopacket {
-        id: "arbitrary proband id".to_string(),
-        subject: Some(individual),
-        phenotypic_features,
-        interpretations: vec![interpretation],
-        meta_data: Some(metadata),
-        ..Default::default()
-    };
+    let phenopacket = PhenopacketBuilder::new("arbitrary proband id")
+        .subject(individual)
+        .phenotypic_features(phenotypic_features)
+        .interpretation(interpretation)
+        .meta_data(metadata)
+        .resource(Resources::hpo_version("2021-08-02"))
+        .resource(Resources::geno_version("2020-03-08"))
+        .resource(Resources::eco_version("2022-08-05"))
+        .resource(Resources::omim_version("2022-11-23"))
+        .build()?;
     Ok(phenopacket)
 
     // phenopacket can now be serialized or used as needed
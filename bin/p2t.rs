@@ -1,7 +1,23 @@
 
 
-use clap::Parser;
-use std::path::Path;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use phenopacket_tools::io::batch::BatchProcessor;
+use phenopacket_tools::io::json_printer::JsonPrinter;
+use phenopacket_tools::io::phenopacket_printer::PhenopacketPrinter;
+use phenopacket_tools::io::phenopacket_reader::PhenopacketFormat;
+use phenopacket_tools::io::yaml_printer::YamlPrinter;
+use phenopacket_tools::ontology::ontology_graph::Ontology;
+use phenopacket_tools::tabular::template::TemplateReader;
+use phenopacket_tools::validation::base_validator::BaseValidator;
+use phenopacket_tools::validation::hierarchy_validator::HierarchyValidator;
+use phenopacket_tools::validation::obsolete_term_validator::ObsoleteTermValidator;
+use phenopacket_tools::validation::phenopacket_validator::ValidationRunner;
+use phenopacket_tools::validation::phenotypic_abnormality_validator::PhenotypicAbnormalityValidator;
+use phenopacket_tools::validation::resource_validator::ResourceValidator;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
 mod bethlam_myopathy;
 use crate::bethlam_myopathy::bethlem_myopathy_phenopacket;
 
@@ -13,26 +29,175 @@ use crate::bethlam_myopathy::bethlem_myopathy_phenopacket;
 #[command(name = "p2t")]
 #[command(about = "Phenopacket tools CLI", long_about = None)]
 struct Cli {
-  /*  /// A required input file
+    /// Case template (pyphetools layout, tab- or comma-separated) to convert to phenopackets
     #[arg(short, long)]
-    template: String,
+    template: Option<PathBuf>,
+
+    /// Directory for the phenopackets of the template, one JSON file each (default: print to stdout)
+    #[arg(short, long, requires = "template")]
+    json: Option<PathBuf>,
 
-    #[arg(short, long)]
-    json: String,
- */
     /// An optional flag
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Validate and/or convert a directory of phenopackets
+    Batch(BatchArgs),
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Directory with the input phenopackets
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Directory for the converted phenopackets and the summary (outside of the input directory when converting)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Also process subdirectories
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Only process files matching the glob pattern (e.g. '*.json'); may be repeated
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Skip files matching the glob pattern; may be repeated
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Convert the phenopackets to this format
+    #[arg(short, long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Validate the phenopackets (base and resource checks)
+    #[arg(long)]
+    validate: bool,
+
+    /// HPO file (hp.json or hp.obo) for the ontology checks; implies --validate
+    #[arg(long)]
+    hpo: Option<PathBuf>,
+
+    /// Number of worker threads (default: one per core)
+    #[arg(long)]
+    threads: Option<usize>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Yaml,
+    Protobuf,
+}
+
+impl From<OutputFormat> for PhenopacketFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Json => PhenopacketFormat::Json,
+            OutputFormat::Yaml => PhenopacketFormat::Yaml,
+            OutputFormat::Protobuf => PhenopacketFormat::Protobuf,
+        }
+    }
+}
+
+
+fn run_batch(args: BatchArgs) -> Result<bool, String> {
+    let mut processor = BatchProcessor::new().recursive(args.recursive);
+    for pattern in &args.include {
+        processor = processor.include(pattern).map_err(|e| e.to_string())?;
+    }
+    for pattern in &args.exclude {
+        processor = processor.exclude(pattern).map_err(|e| e.to_string())?;
+    }
+    if args.validate || args.hpo.is_some() {
+        let mut runner = ValidationRunner::new()
+            .with_validator(BaseValidator)
+            .with_validator(ResourceValidator);
+        if let Some(hpo) = &args.hpo {
+            let hpo = Arc::new(Ontology::from_path(hpo).map_err(|e| e.to_string())?);
+            runner = runner
+                .with_validator(ObsoleteTermValidator::new(hpo.clone()))
+                .with_validator(HierarchyValidator::new(hpo.clone()))
+                .with_validator(PhenotypicAbnormalityValidator::new(hpo));
+        }
+        processor = processor.validate_with(runner);
+    }
+    if let Some(format) = args.format {
+        processor = processor.convert_to(format.into());
+    }
+    if let Some(threads) = args.threads {
+        processor = processor.threads(threads);
+    }
+    let summary = processor.run(&args.input, args.output.as_deref()).map_err(|e| e.to_string())?;
+    print!("{summary}");
+    Ok(summary.failed() == 0)
+}
+
+
+fn run_template(template: &Path, json: Option<&Path>) -> Result<(), String> {
+    let import = TemplateReader::new("p2t").read_path(template).map_err(|e| e.to_string())?;
+    if import.has_errors() {
+        for diagnostic in &import.diagnostics {
+            eprintln!("{diagnostic}");
+        }
+        return Err(format!("{} problems in {}; no phenopackets written", import.diagnostics.len(), template.display()));
+    }
+    let phenopackets = import.phenopackets;
+    let printer = JsonPrinter::pretty();
+    match json {
+        Some(dir) => {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+            for pp in &phenopackets {
+                let path = dir.join(format!("{}.json", pp.id));
+                printer.print_to_path(pp, &path).map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+            }
+            println!("Wrote {} phenopackets to {}", phenopackets.len(), dir.display());
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            for pp in &phenopackets {
+                printer.print(pp, &mut stdout).map_err(|e| e.to_string())?;
+                println!();
+            }
+        }
+    }
+    Ok(())
+}
 
 
-fn main() {
+fn main() -> ExitCode {
     let cli = Cli::parse();
-    let myopathy = bethlem_myopathy_phenopacket();
+    if let Some(Command::Batch(args)) = cli.command {
+        return match run_batch(args) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Some(template) = &cli.template {
+        return match run_template(template, cli.json.as_deref()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    let myopathy = bethlem_myopathy_phenopacket().expect("Could not build the Bethlem myopathy phenopacket");
     println!("{:?}", &myopathy);
-    let json = serde_json::to_string_pretty(&myopathy).unwrap();
-    println!("{}", json);
-    let yaml = serde_yaml::to_string(&myopathy).unwrap();
-    println!("{}", yaml);
+    let mut stdout = io::stdout().lock();
+    JsonPrinter::pretty().print(&myopathy, &mut stdout).unwrap();
+    println!();
+    YamlPrinter.print(&myopathy, &mut stdout).unwrap();
+    ExitCode::SUCCESS
 }
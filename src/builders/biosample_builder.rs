@@ -0,0 +1,200 @@
+//! Fluent builder for Biosample messages
+//!
+//! Besides the general description of a sample (tissue, type, time of collection, procedure), the builder covers
+//! the fields used for cancer samples: histology, tumor progression and grade, pathological stage and TNM findings,
+//! and diagnostic markers.
+use phenopackets::schema::v2::core::{Biosample, File, Measurement, OntologyClass, PhenotypicFeature, Procedure, TimeElement};
+
+use crate::error::{Error, Result};
+
+
+/// Stateful builder for [`Biosample`] messages.
+///
+/// When the biosample is added to a [`crate::builders::phenopacket_builder::PhenopacketBuilder`], its
+/// `individual_id` must be the id of the subject or empty (it is then set to the id of the subject).
+///
+/// ```
+/// use phenopacket_tools::builders::biosample_builder::BiosampleBuilder;
+/// use phenopacket_tools::builders::builder::Builder;
+///
+/// let biosample = BiosampleBuilder::new("biosample 1")
+///     .individual_id("patient 1")
+///     .sampled_tissue(Builder::ontology_class("UBERON:0002048", "lung").unwrap())
+///     .histological_diagnosis(Builder::ontology_class("NCIT:C3493", "Lung Squamous Cell Carcinoma").unwrap())
+///     .pathological_tnm_finding(Builder::ontology_class("NCIT:C48724", "T2 Stage Finding").unwrap())
+///     .build()
+///     .unwrap();
+/// assert_eq!("patient 1", biosample.individual_id);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BiosampleBuilder {
+    biosample: Biosample,
+}
+
+impl BiosampleBuilder {
+    pub fn new(identifier: impl Into<String>) -> Self {
+        BiosampleBuilder {
+            biosample: Biosample { id: identifier.into(), ..Default::default() },
+        }
+    }
+
+    /// The id of the individual from whom the sample was taken.
+    pub fn individual_id(mut self, individual_id: impl Into<String>) -> Self {
+        self.biosample.individual_id = individual_id.into();
+        self
+    }
+
+    /// The id of the biosample this one was derived from (e.g., a slice of a tumor sample).
+    pub fn derived_from_id(mut self, derived_from_id: impl Into<String>) -> Self {
+        self.biosample.derived_from_id = derived_from_id.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.biosample.description = description.into();
+        self
+    }
+
+    /// The tissue from which the sample was taken (an UBERON term).
+    pub fn sampled_tissue(mut self, tissue: OntologyClass) -> Self {
+        self.biosample.sampled_tissue = Some(tissue);
+        self
+    }
+
+    pub fn sample_type(mut self, sample_type: OntologyClass) -> Self {
+        self.biosample.sample_type = Some(sample_type);
+        self
+    }
+
+    pub fn phenotypic_feature(mut self, feature: PhenotypicFeature) -> Self {
+        self.biosample.phenotypic_features.push(feature);
+        self
+    }
+
+    pub fn measurement(mut self, measurement: Measurement) -> Self {
+        self.biosample.measurements.push(measurement);
+        self
+    }
+
+    pub fn taxonomy(mut self, taxonomy: OntologyClass) -> Self {
+        self.biosample.taxonomy = Some(taxonomy);
+        self
+    }
+
+    pub fn time_of_collection(mut self, time_of_collection: TimeElement) -> Self {
+        self.biosample.time_of_collection = Some(time_of_collection);
+        self
+    }
+
+    pub fn histological_diagnosis(mut self, diagnosis: OntologyClass) -> Self {
+        self.biosample.histological_diagnosis = Some(diagnosis);
+        self
+    }
+
+    /// Primary, metastatic, or recurrent tumor (e.g., NCIT:C84509 "Primary Malignant Neoplasm").
+    pub fn tumor_progression(mut self, progression: OntologyClass) -> Self {
+        self.biosample.tumor_progression = Some(progression);
+        self
+    }
+
+    pub fn tumor_grade(mut self, grade: OntologyClass) -> Self {
+        self.biosample.tumor_grade = Some(grade);
+        self
+    }
+
+    pub fn pathological_stage(mut self, stage: OntologyClass) -> Self {
+        self.biosample.pathological_stage = Some(stage);
+        self
+    }
+
+    pub fn pathological_tnm_finding(mut self, finding: OntologyClass) -> Self {
+        self.biosample.pathological_tnm_finding.push(finding);
+        self
+    }
+
+    /// A clinically relevant marker, e.g., NCIT:C68748 "HER2/Neu Positive".
+    pub fn diagnostic_marker(mut self, marker: OntologyClass) -> Self {
+        self.biosample.diagnostic_markers.push(marker);
+        self
+    }
+
+    /// The procedure by which the sample was obtained, e.g., a biopsy.
+    pub fn procedure(mut self, procedure: Procedure) -> Self {
+        self.biosample.procedure = Some(procedure);
+        self
+    }
+
+    pub fn file(mut self, file: File) -> Self {
+        self.biosample.files.push(file);
+        self
+    }
+
+    /// Whether the sample is from abnormal or normal tissue (e.g., EFO:0009655 "abnormal sample").
+    pub fn material_sample(mut self, material_sample: OntologyClass) -> Self {
+        self.biosample.material_sample = Some(material_sample);
+        self
+    }
+
+    pub fn sample_processing(mut self, processing: OntologyClass) -> Self {
+        self.biosample.sample_processing = Some(processing);
+        self
+    }
+
+    pub fn sample_storage(mut self, storage: OntologyClass) -> Self {
+        self.biosample.sample_storage = Some(storage);
+        self
+    }
+
+    /// Create the Biosample. Returns an error if the id is missing.
+    pub fn build(self) -> Result<Biosample> {
+        if self.biosample.id.trim().is_empty() {
+            return Err(Error::PhenopacketError { msg: "Cannot build Biosample without an id".to_string() });
+        }
+        Ok(self.biosample)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::medical_action_builder::MedicalActions;
+    use crate::builders::time_elements::age;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_build_tumor_biosample() -> Result<()> {
+        let lung = Builder::ontology_class("UBERON:0002048", "lung")?;
+        let biopsy = MedicalActions::procedure(Builder::ontology_class("NCIT:C15189", "Biopsy")?, Some(lung.clone()), None);
+        let biosample = BiosampleBuilder::new("sample1")
+            .individual_id("patient1")
+            .sampled_tissue(lung)
+            .sample_type(Builder::ontology_class("OBI:0001479", "specimen from organism")?)
+            .time_of_collection(age("P51Y")?)
+            .histological_diagnosis(Builder::ontology_class("NCIT:C3493", "Lung Squamous Cell Carcinoma")?)
+            .tumor_progression(Builder::ontology_class("NCIT:C84509", "Primary Malignant Neoplasm")?)
+            .tumor_grade(Builder::ontology_class("NCIT:C28080", "Grade 3a")?)
+            .pathological_stage(Builder::ontology_class("NCIT:C27977", "Stage IIIA")?)
+            .pathological_tnm_finding(Builder::ontology_class("NCIT:C48724", "T2 Stage Finding")?)
+            .pathological_tnm_finding(Builder::ontology_class("NCIT:C48706", "N1 Stage Finding")?)
+            .diagnostic_marker(Builder::ontology_class("NCIT:C68748", "HER2/Neu Positive")?)
+            .procedure(biopsy)
+            .material_sample(Builder::ontology_class("EFO:0009655", "abnormal sample")?)
+            .sample_processing(Builder::ontology_class("NCIT:C185127", "Fresh")?)
+            .sample_storage(Builder::ontology_class("NCIT:C185131", "Frozen")?)
+            .build()?;
+        assert_eq!("patient1", biosample.individual_id);
+        assert_eq!(2, biosample.pathological_tnm_finding.len());
+        assert_eq!(1, biosample.diagnostic_markers.len());
+        assert_eq!("Biopsy", biosample.procedure.unwrap().code.unwrap().label);
+        assert_eq!("Frozen", biosample.sample_storage.unwrap().label);
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_missing_id() {
+        let result = BiosampleBuilder::new(" ").individual_id("patient1").build();
+        assert_eq!("Cannot build Biosample without an id", result.unwrap_err().to_string());
+    }
+}
@@ -12,38 +12,47 @@ use phenopackets::schema::v2::core::{AcmgPathogenicityClassification, Diagnosis,
 use phenopackets::schema::v2::Phenopacket;
 use prost_types::Timestamp;
 
+use crate::builders::curie_patterns::DEFAULT_CURIE_PATTERNS;
 use crate::error::{self, Error, Result};
 
-/// A valid curie must have a non-empty prefix and a non-empty numeric suffic
-/// white-space is not allowed.
+/// The Phenopacket Schema version written to MetaData by the builders.
+pub const PHENOPACKET_SCHEMA_VERSION: &str = "2.0.2";
+
+/// A valid CURIE has the form `prefix:local_id`.
+/// The prefix must start with a letter or underscore and may contain letters, digits, '.', '-', and '_'.
+/// The local id must be non-empty and may contain any character except whitespace, control characters,
+/// and further colons, so that ids such as `UCUM:mg.dL-1`, `UCUM:[diop]`, or `ORPHA:123-4` are accepted.
+/// See [`crate::builders::curie_patterns::CuriePatterns`] for prefix-specific checks of the local id.
 pub fn check_valid_curie(s: &str) -> Result<()> {
     if s.is_empty() {
         return Err(Error::CurieError {
             msg: "Empty CURIE".to_string(),
         });
-    } else if let Some(pos) = s.find(':') {
+    } else if let Some((prefix, suffix)) = s.split_once(':') {
         if s.chars().any(|c| c.is_whitespace()) {
             return Err(Error::CurieError {
                 msg: format!("Contains stray whitespace: '{}'", s),
             });
-        } else if s.matches(':').count() != 1 {
+        } else if suffix.contains(':') {
             return Err(Error::CurieError {
-                msg: format!("Invalid CURIE with more than one colon: '{}", s),
+                msg: format!("Invalid CURIE with more than one colon: '{}'", s),
             });
-        } else if pos == 0 {
+        } else if prefix.is_empty() {
             return Err(Error::CurieError {
                 msg: format!("Invalid CURIE with no prefix: '{}'", s),
             });
-        } else if pos == s.len() - 1 {
+        } else if suffix.is_empty() {
             return Err(Error::CurieError {
                 msg: format!("Invalid CURIE with no suffix: '{}'", s),
             });
-        } else if let Some((_prefix, suffix)) = s.split_once(':') {
-            if !suffix.chars().all(char::is_alphanumeric) {
-                return Err(Error::CurieError {
-                    msg: format!("Invalid CURIE with non-alphanumeric characters in suffix: '{}'", s),
-                });
-            }
+        } else if !is_valid_prefix(prefix) {
+            return Err(Error::CurieError {
+                msg: format!("Invalid CURIE prefix: '{}'", s),
+            });
+        } else if suffix.chars().any(char::is_control) {
+            return Err(Error::CurieError {
+                msg: format!("Invalid CURIE with control characters in suffix: '{}'", s),
+            });
         }
     } else {
         return Err(Error::CurieError {
@@ -53,6 +62,17 @@ pub fn check_valid_curie(s: &str) -> Result<()> {
     Ok(())
 }
 
+fn is_valid_prefix(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Return the prefix of a CURIE (e.g., "HP" for "HP:0001629") or None if there is no colon.
+pub fn curie_prefix(s: &str) -> Option<&str> {
+    s.split_once(':').map(|(prefix, _)| prefix)
+}
+
 
 
 
@@ -82,11 +102,13 @@ impl Builder {
         }
     }
 
+    /// Create an OntologyClass. The id must be a valid CURIE that matches the default pattern for its prefix
+    /// (see [`crate::builders::curie_patterns::DEFAULT_CURIE_PATTERNS`]).
     pub fn ontology_class(id: impl Into<String>, label: impl Into<String>) -> Result<OntologyClass> {
         let id:String = id.into();
-        check_valid_curie(&id)?;
+        DEFAULT_CURIE_PATTERNS.check(&id)?;
         Ok(OntologyClass {
-            id: id,
+            id,
             label: label.into(),
         })
     }
@@ -131,22 +153,20 @@ impl Builder {
      /// Create a mosaicism Extension with the given percentage (e.g., 12.5%).
     pub fn mosaicism_extension(percentage: f64) -> Extension {
         let percentage_string = format!("{:.1}%", percentage);
-        let MOSAICISM: &str = "mosaicism";
+        let mosaicism: &str = "mosaicism";
         Extension {
-            name: MOSAICISM.to_string(),
+            name: mosaicism.to_string(),
             value: percentage_string,
-            ..Default::default()
         }
     }
 
     /// Create an allele frequency Extension with the given frequency (in percent).
     pub fn allele_frequency_extension(frequency: f64) -> Extension {
         let percentage_string = format!("{:.1}%", frequency);
-        let ALLELE_FREQUENCY: &str = "allele-frequency";
+        let allele_frequency: &str = "allele-frequency";
         Extension {
-            name: ALLELE_FREQUENCY.to_string(),
+            name: allele_frequency.to_string(),
             value: percentage_string,
-            ..Default::default()
         }
     }
 
@@ -179,12 +199,12 @@ impl Builder {
 
     /// Create builder from ISO8601 string
     pub fn meta_data(created: Timestamp, created_by: impl Into<String>) -> MetaData {
-        let schema_version: &'static str = "2.0.2"; // latest Phenopacket Schema version
-        let mut metadata = MetaData::default();
-        metadata.created = Some(created);
-        metadata.created_by = created_by.into();
-        metadata.phenopacket_schema_version = schema_version.to_string();
-        metadata 
+        MetaData {
+            created: Some(created),
+            created_by: created_by.into(),
+            phenopacket_schema_version: PHENOPACKET_SCHEMA_VERSION.to_string(),
+            ..Default::default()
+        }
     }
 
     /// Create a MetaData message with the current time
@@ -347,9 +367,25 @@ mod tests {
 
 
 
+    #[rstest]
+    #[case("UCUM:mg.dL-1", "milligram per deciliter")]
+    #[case("UCUM:[diop]", "diopter")]
+    #[case("UCUM:mm[Hg]", "millimetres of mercury")]
+    #[case("NCBITaxon:9606_x", "made-up taxon")]
+    #[case("ORPHA:123-4", "made-up disease")]
+    fn test_valid_non_alphanumeric_terms(
+        #[case] term_id: &str,
+        #[case] term_label: &str,
+    ) {
+        assert!(Builder::ontology_class(term_id, term_label).is_ok());
+    }
+
     #[rstest]
     #[case("HP0002162", "Low posterior hairline", "Invalid CURIE with no colon: 'HP0002162'")]
     #[case("MONDO:0017309 ", "neonatal Marfan syndrome", "Contains stray whitespace: 'MONDO:0017309 '")]
+    #[case("HP:12", "Low posterior hairline", "Invalid CURIE 'HP:12': local id does not match the pattern for HP (\\d{7})")]
+    #[case("1HP:0002162", "Low posterior hairline", "Invalid CURIE prefix: '1HP:0002162'")]
+    #[case("HP:0002:162", "Low posterior hairline", "Invalid CURIE with more than one colon: 'HP:0002:162'")]
     fn test_invalid_terms(
         #[case] term_id: &str, 
         #[case] term_label: &str, 
@@ -386,12 +422,12 @@ mod tests {
 
     fn approx_equal(a: f64, b: f64) -> bool
     {
-        (a-b).abs() < std::f64::EPSILON
+        (a-b).abs() < f64::EPSILON
     }
 
     #[rstest]
     fn test_mosaicism() {
-        let percentage = 42 as f64;
+        let percentage = 42_f64;
         let extension = Builder::mosaicism_extension(percentage);
         assert_eq!("mosaicism", extension.name);
         assert_eq!("42.0%", extension.value);
@@ -400,7 +436,7 @@ mod tests {
 
     #[rstest]
     fn test_allele_frequency() {
-        let percentage = 23 as f64;
+        let percentage = 23_f64;
         let extension = Builder::allele_frequency_extension(percentage);
         assert_eq!("allele-frequency", extension.name);
         assert_eq!("23.0%", extension.value);
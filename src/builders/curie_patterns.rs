@@ -0,0 +1,123 @@
+//! Prefix-specific patterns for the local id of CURIEs
+//!
+//! [`check_valid_curie`] only checks the general CURIE syntax. A [`CuriePatterns`] registry additionally checks
+//! the local id of known prefixes, so that malformed ids such as `HP:12` are rejected.
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::builders::builder::check_valid_curie;
+use crate::error::{Error, Result};
+
+
+lazy_static! {
+    /// Patterns for commonly used prefixes whose local ids have a fixed format.
+    pub static ref DEFAULT_CURIE_PATTERNS: CuriePatterns = CuriePatterns::default();
+}
+
+
+/// Registry of regular expressions for the local id of CURIEs, indexed by prefix.
+#[derive(Debug, Clone)]
+pub struct CuriePatterns {
+    /// prefix -> (local id pattern as registered, anchored regex)
+    patterns: HashMap<String, (String, Regex)>,
+}
+
+impl Default for CuriePatterns {
+    fn default() -> Self {
+        let mut patterns = Self::empty();
+        for (prefix, pattern) in [
+            ("HP", r"\d{7}"),
+            ("MONDO", r"\d{7}"),
+            ("OMIM", r"\d{6}"),
+            ("GENO", r"\d{7}"),
+            ("ECO", r"\d{7}"),
+            ("UBERON", r"\d{7}"),
+            ("NCIT", r"C\d+"),
+            ("HGNC", r"\d+"),
+        ] {
+            patterns.register(prefix, pattern).expect("default CURIE patterns must be valid");
+        }
+        patterns
+    }
+}
+
+impl CuriePatterns {
+    /// A registry without any patterns (only the general CURIE syntax is checked).
+    pub fn empty() -> Self {
+        CuriePatterns { patterns: HashMap::new() }
+    }
+
+    /// Register the pattern for the local id of a prefix, e.g., `register("HP", r"\d{7}")`.
+    /// The pattern must match the entire local id. Replaces any previous pattern for the prefix.
+    pub fn register(&mut self, prefix: impl Into<String>, local_id_pattern: &str) -> Result<()> {
+        let prefix = prefix.into();
+        let regex = Regex::new(&format!("^(?:{local_id_pattern})$")).map_err(|e| Error::CurieError {
+            msg: format!("Invalid pattern for prefix {prefix}: {e}"),
+        })?;
+        self.patterns.insert(prefix, (local_id_pattern.to_string(), regex));
+        Ok(())
+    }
+
+    /// Get the registered local id pattern for a prefix.
+    pub fn pattern(&self, prefix: &str) -> Option<&str> {
+        self.patterns.get(prefix).map(|(pattern, _)| pattern.as_str())
+    }
+
+    /// Check the general CURIE syntax and, if a pattern is registered for the prefix, the local id.
+    pub fn check(&self, curie: &str) -> Result<()> {
+        check_valid_curie(curie)?;
+        if let Some((prefix, local_id)) = curie.split_once(':') {
+            if let Some((pattern, regex)) = self.patterns.get(prefix) {
+                if !regex.is_match(local_id) {
+                    return Err(Error::CurieError {
+                        msg: format!("Invalid CURIE '{curie}': local id does not match the pattern for {prefix} ({pattern})"),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("HP:0001629")]
+    #[case("MONDO:0017309")]
+    #[case("OMIM:158810")]
+    #[case("NCIT:C2926")]
+    #[case("UCUM:mg.dL-1")]
+    #[case("ORPHA:123-4")]
+    fn test_valid_curies(#[case] curie: &str) {
+        assert!(DEFAULT_CURIE_PATTERNS.check(curie).is_ok());
+    }
+
+    #[rstest]
+    #[case("HP:12")]
+    #[case("HP:00016290")]
+    #[case("OMIM:15881")]
+    #[case("NCIT:2926")]
+    #[case("MONDO:abcdefg")]
+    fn test_invalid_local_ids(#[case] curie: &str) {
+        let result = DEFAULT_CURIE_PATTERNS.check(curie);
+        assert!(matches!(result, Err(Error::CurieError { .. })));
+    }
+
+    #[rstest]
+    fn test_register() {
+        let mut patterns = CuriePatterns::empty();
+        assert!(patterns.check("ORPHA:123-4").is_ok());
+        patterns.register("ORPHA", r"\d+").unwrap();
+        assert_eq!(Some(r"\d+"), patterns.pattern("ORPHA"));
+        assert_eq!(
+            r"Invalid CURIE 'ORPHA:123-4': local id does not match the pattern for ORPHA (\d+)",
+            patterns.check("ORPHA:123-4").unwrap_err().to_string());
+        assert!(patterns.register("ORPHA", r"(\d+").is_err());
+    }
+}
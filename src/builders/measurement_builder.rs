@@ -0,0 +1,215 @@
+//! Fluent builder for Measurement messages
+//!
+//! A Measurement has an assay (usually a LOINC term) and exactly one value: a [`Quantity`] (optionally with a
+//! [`ReferenceRange`]), an ontology class, or a [`ComplexValue`] of typed quantities such as the systolic and
+//! diastolic blood pressure.
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::{
+    value, ComplexValue, Measurement, OntologyClass, Procedure, Quantity, ReferenceRange, TimeElement, TypedQuantity,
+    Value,
+};
+
+use crate::constants::units::Unit;
+use crate::error::{Error, Result};
+
+
+impl Error {
+    fn invalid_measurement(assay: &OntologyClass, reason: &str) -> Self {
+        Error::MeasurementError { msg: format!("Cannot build Measurement of {}: {reason}", assay.label) }
+    }
+}
+
+
+/// Stateful builder for [`Measurement`] messages.
+///
+/// ```
+/// use phenopacket_tools::builders::builder::Builder;
+/// use phenopacket_tools::builders::measurement_builder::MeasurementBuilder;
+/// use phenopacket_tools::builders::time_elements::age;
+/// use phenopacket_tools::constants::units::Unit;
+///
+/// let assay = Builder::ontology_class("LOINC:17861-6", "Calcium [Mass/volume] in Serum or Plasma").unwrap();
+/// let calcium = MeasurementBuilder::new(assay)
+///     .quantity(7.1, Unit::milligram_per_deciliter())
+///     .reference_range(8.5, 10.5)
+///     .time_observed(age("P3Y").unwrap())
+///     .build()
+///     .unwrap();
+///
+/// let blood_pressure = MeasurementBuilder::blood_pressure(125.0, 75.0).build().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MeasurementBuilder {
+    assay: OntologyClass,
+    description: String,
+    quantity: Option<Quantity>,
+    reference_range: Option<(f64, f64)>,
+    ontology_value: Option<OntologyClass>,
+    typed_quantities: Vec<TypedQuantity>,
+    time_observed: Option<TimeElement>,
+    procedure: Option<Procedure>,
+}
+
+impl MeasurementBuilder {
+    pub fn new(assay: OntologyClass) -> Self {
+        MeasurementBuilder {
+            assay,
+            ..Default::default()
+        }
+    }
+
+    /// A blood pressure panel (LOINC:85354-9) with the systolic and diastolic pressure in mmHg.
+    pub fn blood_pressure(systolic: f64, diastolic: f64) -> Self {
+        let loinc = |id: &str, label: &str| OntologyClass { id: id.to_string(), label: label.to_string() };
+        Self::new(loinc("LOINC:85354-9", "Blood pressure panel with all children optional"))
+            .typed_quantity(loinc("LOINC:8480-6", "Systolic blood pressure"), systolic, Unit::mm_hg())
+            .typed_quantity(loinc("LOINC:8462-4", "Diastolic blood pressure"), diastolic, Unit::mm_hg())
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Set a numeric value.
+    pub fn quantity(mut self, value: f64, unit: OntologyClass) -> Self {
+        self.quantity = Some(Quantity { unit: Some(unit), value, reference_range: None });
+        self
+    }
+
+    /// Set the normal range of the quantity, in the unit of the quantity.
+    pub fn reference_range(mut self, low: f64, high: f64) -> Self {
+        self.reference_range = Some((low, high));
+        self
+    }
+
+    /// Set a categorical value, e.g., "present" or "abnormal".
+    pub fn ontology_value(mut self, value: OntologyClass) -> Self {
+        self.ontology_value = Some(value);
+        self
+    }
+
+    /// Add a component of a complex value, e.g., the systolic blood pressure.
+    pub fn typed_quantity(mut self, r#type: OntologyClass, value: f64, unit: OntologyClass) -> Self {
+        self.typed_quantities.push(TypedQuantity {
+            r#type: Some(r#type),
+            quantity: Some(Quantity { unit: Some(unit), value, reference_range: None }),
+        });
+        self
+    }
+
+    pub fn time_observed(mut self, time_observed: TimeElement) -> Self {
+        self.time_observed = Some(time_observed);
+        self
+    }
+
+    pub fn procedure(mut self, procedure: Procedure) -> Self {
+        self.procedure = Some(procedure);
+        self
+    }
+
+    /// Create the Measurement. Returns an error unless exactly one kind of value is set, if a reference range
+    /// is set without a quantity, or if the reference range is empty.
+    pub fn build(self) -> Result<Measurement> {
+        let kinds = [self.quantity.is_some(), self.ontology_value.is_some(), !self.typed_quantities.is_empty()];
+        match kinds.iter().filter(|&&set| set).count() {
+            0 => return Err(Error::invalid_measurement(&self.assay, "no value")),
+            1 => {}
+            _ => return Err(Error::invalid_measurement(&self.assay, "more than one kind of value")),
+        }
+        let measurement_value = if let Some(mut quantity) = self.quantity {
+            if let Some((low, high)) = self.reference_range {
+                if low.is_nan() || high.is_nan() || low > high {
+                    return Err(Error::invalid_measurement(&self.assay, &format!("invalid reference range {low}-{high}")));
+                }
+                quantity.reference_range = Some(ReferenceRange { unit: quantity.unit.clone(), low, high });
+            }
+            MeasurementValue::Value(Value { value: Some(value::Value::Quantity(quantity)) })
+        } else if self.reference_range.is_some() {
+            return Err(Error::invalid_measurement(&self.assay, "reference range without a quantity"));
+        } else if let Some(ontology_value) = self.ontology_value {
+            MeasurementValue::Value(Value { value: Some(value::Value::OntologyClass(ontology_value)) })
+        } else {
+            MeasurementValue::ComplexValue(ComplexValue { typed_quantities: self.typed_quantities })
+        };
+        Ok(Measurement {
+            description: self.description,
+            assay: Some(self.assay),
+            time_observed: self.time_observed,
+            procedure: self.procedure,
+            measurement_value: Some(measurement_value),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::time_elements::age;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn calcium() -> OntologyClass {
+        Builder::ontology_class("LOINC:17861-6", "Calcium [Mass/volume] in Serum or Plasma").unwrap()
+    }
+
+    #[rstest]
+    fn test_quantity_with_reference_range(calcium: OntologyClass) -> Result<()> {
+        let measurement = MeasurementBuilder::new(calcium.clone())
+            .quantity(7.1, Unit::milligram_per_deciliter())
+            .reference_range(8.5, 10.5)
+            .time_observed(age("P3Y")?)
+            .build()?;
+        assert_eq!(Some(calcium), measurement.assay);
+        assert!(measurement.time_observed.is_some());
+        let Some(MeasurementValue::Value(Value { value: Some(value::Value::Quantity(quantity)) })) =
+            measurement.measurement_value
+        else {
+            panic!("expected a quantity");
+        };
+        assert_eq!(7.1, quantity.value);
+        let range = quantity.reference_range.unwrap();
+        assert_eq!((Some(Unit::milligram_per_deciliter()), 8.5, 10.5), (range.unit, range.low, range.high));
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_ontology_value(calcium: OntologyClass) -> Result<()> {
+        let abnormal = Builder::ontology_class("NCIT:C25401", "Abnormal")?;
+        let measurement = MeasurementBuilder::new(calcium).ontology_value(abnormal.clone()).build()?;
+        assert_eq!(
+            Some(MeasurementValue::Value(Value { value: Some(value::Value::OntologyClass(abnormal)) })),
+            measurement.measurement_value);
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_blood_pressure() -> Result<()> {
+        let measurement = MeasurementBuilder::blood_pressure(125.0, 75.0).build()?;
+        let Some(MeasurementValue::ComplexValue(complex)) = measurement.measurement_value else {
+            panic!("expected a complex value");
+        };
+        let values: Vec<(String, f64)> = complex
+            .typed_quantities
+            .into_iter()
+            .map(|tq| (tq.r#type.unwrap().label, tq.quantity.unwrap().value))
+            .collect();
+        assert_eq!(
+            vec![("Systolic blood pressure".to_string(), 125.0), ("Diastolic blood pressure".to_string(), 75.0)],
+            values);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(MeasurementBuilder::new(calcium()), "no value")]
+    #[case(MeasurementBuilder::new(calcium()).quantity(1.0, Unit::mole()).ontology_value(calcium()), "more than one kind of value")]
+    #[case(MeasurementBuilder::new(calcium()).quantity(1.0, Unit::mole()).reference_range(2.0, 1.0), "invalid reference range 2-1")]
+    #[case(MeasurementBuilder::new(calcium()).ontology_value(calcium()).reference_range(1.0, 2.0), "reference range without a quantity")]
+    fn test_invalid_measurement(#[case] builder: MeasurementBuilder, #[case] reason: &str) {
+        let error = builder.build().unwrap_err();
+        assert!(matches!(error, Error::MeasurementError { .. }));
+        assert_eq!(format!("Cannot build Measurement of Calcium [Mass/volume] in Serum or Plasma: {reason}"), error.to_string());
+    }
+}
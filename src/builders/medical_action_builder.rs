@@ -0,0 +1,274 @@
+//! Builders for MedicalAction messages
+//!
+//! [`MedicalActions`] creates the four kinds of action (Treatment, Procedure, RadiationTherapy, and
+//! TherapeuticRegimen), and [`MedicalActionBuilder`] wraps one of them in a [`MedicalAction`] together with the
+//! fields that apply to all kinds (target, intent, response, adverse events, and termination reason).
+use phenopackets::schema::v2::core::medical_action::Action;
+use phenopackets::schema::v2::core::therapeutic_regimen::{Identifier, RegimenStatus};
+use phenopackets::schema::v2::core::{
+    DoseInterval, DrugType, ExternalReference, MedicalAction, OntologyClass, Procedure, Quantity, RadiationTherapy,
+    TherapeuticRegimen, TimeElement, TimeInterval, Treatment,
+};
+
+
+/// Constructors for the actions of a [`MedicalAction`].
+pub struct MedicalActions;
+
+impl MedicalActions {
+    /// A treatment with an agent (e.g., a DrugCentral or CHEBI term).
+    pub fn treatment(
+        agent: OntologyClass,
+        route_of_administration: Option<OntologyClass>,
+        dose_intervals: Vec<DoseInterval>,
+        drug_type: DrugType,
+    ) -> Treatment {
+        Treatment {
+            agent: Some(agent),
+            route_of_administration,
+            dose_intervals,
+            drug_type: drug_type.into(),
+            cumulative_dose: None,
+        }
+    }
+
+    /// A dose given at the schedule frequency (e.g., NCIT:C64496 "Twice Daily") during the interval.
+    pub fn dose_interval(quantity: Quantity, schedule_frequency: OntologyClass, interval: TimeInterval) -> DoseInterval {
+        DoseInterval {
+            quantity: Some(quantity),
+            schedule_frequency: Some(schedule_frequency),
+            interval: Some(interval),
+        }
+    }
+
+    /// A procedure (e.g., an NCIT term), optionally with the body site (UBERON) and the time it was performed.
+    pub fn procedure(code: OntologyClass, body_site: Option<OntologyClass>, performed: Option<TimeElement>) -> Procedure {
+        Procedure { code: Some(code), body_site, performed }
+    }
+
+    /// A radiation therapy with the total dosage (in Gy) given in the number of fractions.
+    pub fn radiation_therapy(modality: OntologyClass, body_site: OntologyClass, dosage: i32, fractions: i32) -> RadiationTherapy {
+        RadiationTherapy {
+            modality: Some(modality),
+            body_site: Some(body_site),
+            dosage,
+            fractions,
+        }
+    }
+
+    /// A therapeutic regimen identified by an external reference (e.g., a clinical protocol).
+    pub fn therapeutic_regimen_from_reference(
+        reference: ExternalReference,
+        start_time: Option<TimeElement>,
+        end_time: Option<TimeElement>,
+        status: RegimenStatus,
+    ) -> TherapeuticRegimen {
+        Self::therapeutic_regimen(Identifier::ExternalReference(reference), start_time, end_time, status)
+    }
+
+    /// A therapeutic regimen identified by an ontology class.
+    pub fn therapeutic_regimen_from_ontology_class(
+        ontology_class: OntologyClass,
+        start_time: Option<TimeElement>,
+        end_time: Option<TimeElement>,
+        status: RegimenStatus,
+    ) -> TherapeuticRegimen {
+        Self::therapeutic_regimen(Identifier::OntologyClass(ontology_class), start_time, end_time, status)
+    }
+
+    fn therapeutic_regimen(
+        identifier: Identifier,
+        start_time: Option<TimeElement>,
+        end_time: Option<TimeElement>,
+        status: RegimenStatus,
+    ) -> TherapeuticRegimen {
+        TherapeuticRegimen {
+            start_time,
+            end_time,
+            regimen_status: status.into(),
+            identifier: Some(identifier),
+        }
+    }
+}
+
+
+/// Stateful builder for [`MedicalAction`] messages.
+///
+/// ```
+/// use phenopacket_tools::builders::builder::Builder;
+/// use phenopacket_tools::builders::medical_action_builder::{MedicalActionBuilder, MedicalActions};
+/// use phenopackets::schema::v2::core::DrugType;
+///
+/// let agent = Builder::ontology_class("DrugCentral:1610", "losartan").unwrap();
+/// let treatment = MedicalActions::treatment(agent, None, vec![], DrugType::Prescription);
+/// let action = MedicalActionBuilder::treatment(treatment)
+///     .treatment_target(Builder::ontology_class("MONDO:0007947", "Marfan syndrome").unwrap())
+///     .adverse_event(Builder::ontology_class("HP:0002315", "Headache").unwrap())
+///     .build();
+/// assert_eq!(1, action.adverse_events.len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MedicalActionBuilder {
+    action: Action,
+    treatment_target: Option<OntologyClass>,
+    treatment_intent: Option<OntologyClass>,
+    response_to_treatment: Option<OntologyClass>,
+    adverse_events: Vec<OntologyClass>,
+    treatment_termination_reason: Option<OntologyClass>,
+}
+
+impl MedicalActionBuilder {
+    fn new(action: Action) -> Self {
+        MedicalActionBuilder {
+            action,
+            treatment_target: None,
+            treatment_intent: None,
+            response_to_treatment: None,
+            adverse_events: vec![],
+            treatment_termination_reason: None,
+        }
+    }
+
+    pub fn treatment(treatment: Treatment) -> Self {
+        Self::new(Action::Treatment(treatment))
+    }
+
+    pub fn procedure(procedure: Procedure) -> Self {
+        Self::new(Action::Procedure(procedure))
+    }
+
+    pub fn radiation_therapy(radiation_therapy: RadiationTherapy) -> Self {
+        Self::new(Action::RadiationTherapy(radiation_therapy))
+    }
+
+    pub fn therapeutic_regimen(therapeutic_regimen: TherapeuticRegimen) -> Self {
+        Self::new(Action::TherapeuticRegimen(therapeutic_regimen))
+    }
+
+    /// The condition or disease the action was meant to treat.
+    pub fn treatment_target(mut self, target: OntologyClass) -> Self {
+        self.treatment_target = Some(target);
+        self
+    }
+
+    /// The intent of the action, e.g., curative or palliative.
+    pub fn treatment_intent(mut self, intent: OntologyClass) -> Self {
+        self.treatment_intent = Some(intent);
+        self
+    }
+
+    pub fn response_to_treatment(mut self, response: OntologyClass) -> Self {
+        self.response_to_treatment = Some(response);
+        self
+    }
+
+    pub fn adverse_event(mut self, adverse_event: OntologyClass) -> Self {
+        self.adverse_events.push(adverse_event);
+        self
+    }
+
+    pub fn adverse_events(mut self, adverse_events: impl IntoIterator<Item = OntologyClass>) -> Self {
+        self.adverse_events.extend(adverse_events);
+        self
+    }
+
+    pub fn treatment_termination_reason(mut self, reason: OntologyClass) -> Self {
+        self.treatment_termination_reason = Some(reason);
+        self
+    }
+
+    pub fn build(self) -> MedicalAction {
+        MedicalAction {
+            treatment_target: self.treatment_target,
+            treatment_intent: self.treatment_intent,
+            response_to_treatment: self.response_to_treatment,
+            adverse_events: self.adverse_events,
+            treatment_termination_reason: self.treatment_termination_reason,
+            action: Some(self.action),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::time_elements::{age, timestamp_from_str};
+    use crate::constants::units::Unit;
+    use crate::error::Result;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_treatment_with_dose_intervals() -> Result<()> {
+        let interval = TimeInterval {
+            start: Some(timestamp_from_str("2020-03-15T00:00:00Z")?),
+            end: Some(timestamp_from_str("2021-03-15T00:00:00Z")?),
+        };
+        let dose = Quantity { unit: Some(Unit::milligram()), value: 30.0, reference_range: None };
+        let twice_daily = Builder::ontology_class("NCIT:C64496", "Twice Daily")?;
+        let oral = Builder::ontology_class("NCIT:C38288", "Oral Route of Administration")?;
+        let treatment = MedicalActions::treatment(
+            Builder::ontology_class("DrugCentral:1610", "losartan")?,
+            Some(oral.clone()),
+            vec![MedicalActions::dose_interval(dose, twice_daily, interval)],
+            DrugType::Prescription);
+        let action = MedicalActionBuilder::treatment(treatment)
+            .treatment_intent(Builder::ontology_class("NCIT:C62220", "Cure")?)
+            .response_to_treatment(Builder::ontology_class("NCIT:C123584", "Favorable Response")?)
+            .build();
+        let Some(Action::Treatment(treatment)) = action.action else {
+            panic!("expected a treatment");
+        };
+        assert_eq!(Some(oral), treatment.route_of_administration);
+        assert_eq!(DrugType::Prescription, treatment.drug_type());
+        assert_eq!(30.0, treatment.dose_intervals[0].quantity.as_ref().unwrap().value);
+        assert_eq!("Cure", action.treatment_intent.unwrap().label);
+        assert_eq!("Favorable Response", action.response_to_treatment.unwrap().label);
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_procedure_and_radiation_therapy() -> Result<()> {
+        let uberon_lung = Builder::ontology_class("UBERON:0002048", "lung")?;
+        let biopsy = MedicalActions::procedure(
+            Builder::ontology_class("NCIT:C15189", "Biopsy")?,
+            Some(uberon_lung.clone()),
+            Some(age("P51Y")?));
+        let action = MedicalActionBuilder::procedure(biopsy).build();
+        assert!(matches!(&action.action, Some(Action::Procedure(p)) if p.performed.is_some()));
+
+        let photon = Builder::ontology_class("NCIT:C157823", "Photon Beam Radiation Therapy")?;
+        let radiation = MedicalActions::radiation_therapy(photon, uberon_lung, 60, 30);
+        let action = MedicalActionBuilder::radiation_therapy(radiation)
+            .adverse_events([Builder::ontology_class("HP:0012735", "Cough")?])
+            .treatment_termination_reason(Builder::ontology_class("NCIT:C41331", "Adverse Event")?)
+            .build();
+        let Some(Action::RadiationTherapy(radiation)) = &action.action else {
+            panic!("expected a radiation therapy");
+        };
+        assert_eq!((60, 30), (radiation.dosage, radiation.fractions));
+        assert_eq!(1, action.adverse_events.len());
+        assert!(action.treatment_termination_reason.is_some());
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_therapeutic_regimen() -> Result<()> {
+        let reference = Builder::external_reference("NCT04576156", "Trial of cisplatin and etoposide");
+        let regimen = MedicalActions::therapeutic_regimen_from_reference(
+            reference.clone(),
+            Some(age("P50Y")?),
+            None,
+            RegimenStatus::Started);
+        let action = MedicalActionBuilder::therapeutic_regimen(regimen)
+            .treatment_target(Builder::ontology_class("NCIT:C3493", "Lung Squamous Cell Carcinoma")?)
+            .build();
+        let Some(Action::TherapeuticRegimen(regimen)) = action.action else {
+            panic!("expected a therapeutic regimen");
+        };
+        assert_eq!(Some(Identifier::ExternalReference(reference)), regimen.identifier);
+        assert_eq!(RegimenStatus::Started, regimen.regimen_status());
+        assert!(regimen.start_time.is_some() && regimen.end_time.is_none());
+        Ok(())
+    }
+}
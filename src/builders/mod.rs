@@ -1,7 +1,16 @@
 //! Builders to construct and Q/C the major messages of the GA4GH Phenopacket Schema
 //! 
 //! This module contain
+pub mod biosample_builder;
+pub mod curie_patterns;
 pub mod expressions;
+pub mod measurement_builder;
+pub mod medical_action_builder;
+pub mod ontology_class_builder;
+pub mod phenopacket_builder;
+pub mod resource_registry;
 pub mod resources;
 pub mod time_elements;
-pub mod builder;
\ No newline at end of file
+pub mod builder;
+#[cfg(test)]
+pub(crate) mod test_phenopackets;
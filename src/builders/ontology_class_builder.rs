@@ -0,0 +1,131 @@
+//! Create OntologyClass messages with the canonical label of a locally loaded ontology
+use phenopackets::schema::v2::core::OntologyClass;
+
+use crate::builders::curie_patterns::DEFAULT_CURIE_PATTERNS;
+use crate::error::{Error, Result};
+use crate::ontology::ontology_graph::Ontology;
+use crate::ontology::term::Term;
+
+
+/// Build OntologyClass messages from ids or labels, using an [`Ontology`] to fill in the canonical id and label.
+///
+/// ```no_run
+/// use phenopacket_tools::builders::ontology_class_builder::OntologyClassBuilder;
+/// use phenopacket_tools::ontology::ontology_graph::Ontology;
+///
+/// let hpo = Ontology::from_path("hp.json").unwrap();
+/// let builder = OntologyClassBuilder::new(&hpo);
+/// let vsd = builder.ontology_class_from_id("HP:0001629").unwrap();
+/// assert_eq!("Ventricular septal defect", vsd.label);
+/// ```
+pub struct OntologyClassBuilder<'a> {
+    ontology: &'a Ontology,
+}
+
+impl<'a> OntologyClassBuilder<'a> {
+    pub fn new(ontology: &'a Ontology) -> Self {
+        OntologyClassBuilder { ontology }
+    }
+
+    /// Create an OntologyClass with the primary id and label of the term with the given (primary or alternate) id.
+    /// Returns an error if the id is not a valid CURIE, is not in the ontology, or refers to an obsolete term.
+    pub fn ontology_class_from_id(&self, id: &str) -> Result<OntologyClass> {
+        DEFAULT_CURIE_PATTERNS.check(id)?;
+        let term = self
+            .ontology
+            .term_by_any_id(id)
+            .ok_or_else(|| Error::ontology(format!("Could not find '{id}' in ontology")))?;
+        if term.obsolete {
+            let msg = match &term.replaced_by {
+                Some(replacement) => format!("'{id}' is obsolete (replaced by {replacement})"),
+                None => format!("'{id}' is obsolete"),
+            };
+            return Err(Error::ontology(msg));
+        }
+        Ok(Self::to_ontology_class(term))
+    }
+
+    /// Create an OntologyClass from a primary label or, if no label matches, a synonym (case-insensitive).
+    /// Returns an error listing the candidates if the label matches more than one term.
+    pub fn ontology_class_from_label(&self, label: &str) -> Result<OntologyClass> {
+        let mut candidates = self.ontology.terms_by_label(label);
+        if candidates.is_empty() {
+            candidates = self.ontology.terms_by_synonym(label);
+        }
+        match candidates.as_slice() {
+            [] => Err(Error::ontology(format!("Could not find a term with label or synonym '{label}'"))),
+            [term] => Ok(Self::to_ontology_class(term)),
+            _ => {
+                let mut candidates: Vec<String> = candidates
+                    .iter()
+                    .map(|t| format!("{} ({})", t.id, t.label))
+                    .collect();
+                candidates.sort();
+                Err(Error::ontology(format!("Ambiguous label '{label}'; candidates: {}", candidates.join(", "))))
+            }
+        }
+    }
+
+    fn to_ontology_class(term: &Term) -> OntologyClass {
+        OntologyClass {
+            id: term.id.clone(),
+            label: term.label.clone(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::test_ontology::toy_hpo;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("HP:0001629", "HP:0001629", "Ventricular septal defect")]
+    #[case("HP:0001630", "HP:0001629", "Ventricular septal defect")]
+    #[case("HP:0000518", "HP:0000518", "Cataract")]
+    fn test_from_id(#[case] id: &str, #[case] primary_id: &str, #[case] label: &str) {
+        let hpo = toy_hpo();
+        let clz = OntologyClassBuilder::new(&hpo).ontology_class_from_id(id).unwrap();
+        assert_eq!(primary_id, clz.id);
+        assert_eq!(label, clz.label);
+    }
+
+    #[rstest]
+    #[case("HP:9999999", "Could not find 'HP:9999999' in ontology")]
+    #[case("HP:0006000", "'HP:0006000' is obsolete (replaced by HP:0001629)")]
+    #[case("HP0001629", "Invalid CURIE with no colon: 'HP0001629'")]
+    fn test_from_id_error(#[case] id: &str, #[case] error_msg: &str) {
+        let hpo = toy_hpo();
+        let result = OntologyClassBuilder::new(&hpo).ontology_class_from_id(id);
+        assert_eq!(error_msg, result.unwrap_err().to_string());
+    }
+
+    #[rstest]
+    #[case("Ventricular septal defect", "HP:0001629")]
+    #[case("ventricular septal defect", "HP:0001629")]
+    #[case("VSD", "HP:0001629")]
+    #[case("Clouding of the lens", "HP:0000518")]
+    fn test_from_label(#[case] label: &str, #[case] id: &str) {
+        let hpo = toy_hpo();
+        let clz = OntologyClassBuilder::new(&hpo).ontology_class_from_label(label).unwrap();
+        assert_eq!(id, clz.id);
+    }
+
+    #[rstest]
+    fn test_ambiguous_label() {
+        let hpo = toy_hpo();
+        let result = OntologyClassBuilder::new(&hpo).ontology_class_from_label("Cardiovascular anomaly");
+        assert_eq!(
+            "Ambiguous label 'Cardiovascular anomaly'; candidates: HP:0001627 (Abnormal heart morphology), HP:0030680 (Abnormality of cardiovascular system morphology)",
+            result.unwrap_err().to_string());
+    }
+
+    #[rstest]
+    fn test_unknown_label() {
+        let hpo = toy_hpo();
+        let result = OntologyClassBuilder::new(&hpo).ontology_class_from_label("Ventricular hole");
+        assert!(matches!(result, Err(Error::OntologyError { .. })));
+    }
+}
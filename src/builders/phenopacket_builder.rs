@@ -0,0 +1,240 @@
+//! Fluent builder that assembles a complete GA4GH Phenopacket
+//!
+//! The builder collects the individual messages of a phenopacket and checks
+//! on [`PhenopacketBuilder::build`] that the mandatory elements (id, subject, MetaData) are present
+//! and that the biosamples belong to the subject.
+use phenopackets::schema::v2::core::{Biosample, Disease, File, Individual, Interpretation, Measurement, MedicalAction, MetaData, PhenotypicFeature, Resource};
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::error::{Error, Result};
+
+
+impl Error {
+    fn missing_phenopacket_element(element: &str) -> Self {
+        Error::PhenopacketError { msg: format!("Cannot build Phenopacket without {element}") }
+    }
+
+    fn biosample_of_other_individual(biosample: &Biosample, subject: &Individual) -> Self {
+        Error::PhenopacketError {
+            msg: format!(
+                "Biosample '{}' is from individual '{}' but the subject is '{}'",
+                biosample.id, biosample.individual_id, subject.id),
+        }
+    }
+}
+
+
+/// Stateful builder for [`Phenopacket`] messages.
+///
+/// ```
+/// use phenopacket_tools::builders::builder::Builder;
+/// use phenopacket_tools::builders::phenopacket_builder::PhenopacketBuilder;
+/// use phenopacket_tools::builders::resources::Resources;
+///
+/// let hp = Builder::ontology_class("HP:0001629", "Ventricular septal defect").unwrap();
+/// let phenopacket = PhenopacketBuilder::new("proband A")
+///     .subject(Builder::individual("individual A"))
+///     .meta_data(Builder::meta_data_now("anonymous biocurator"))
+///     .phenotypic_feature(Builder::phenotypic_feature_observed(hp))
+///     .resource(Resources::hpo_version("2025-03-03"))
+///     .build()
+///     .unwrap();
+/// assert_eq!(1, phenopacket.phenotypic_features.len());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhenopacketBuilder {
+    id: String,
+    subject: Option<Individual>,
+    phenotypic_features: Vec<PhenotypicFeature>,
+    measurements: Vec<Measurement>,
+    biosamples: Vec<Biosample>,
+    interpretations: Vec<Interpretation>,
+    diseases: Vec<Disease>,
+    medical_actions: Vec<MedicalAction>,
+    files: Vec<File>,
+    meta_data: Option<MetaData>,
+    resources: Vec<Resource>,
+}
+
+impl PhenopacketBuilder {
+    pub fn new(identifier: impl Into<String>) -> Self {
+        PhenopacketBuilder {
+            id: identifier.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn subject(mut self, subject: Individual) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    pub fn phenotypic_feature(mut self, feature: PhenotypicFeature) -> Self {
+        self.phenotypic_features.push(feature);
+        self
+    }
+
+    pub fn phenotypic_features(mut self, features: impl IntoIterator<Item = PhenotypicFeature>) -> Self {
+        self.phenotypic_features.extend(features);
+        self
+    }
+
+    pub fn measurement(mut self, measurement: Measurement) -> Self {
+        self.measurements.push(measurement);
+        self
+    }
+
+    pub fn biosample(mut self, biosample: Biosample) -> Self {
+        self.biosamples.push(biosample);
+        self
+    }
+
+    pub fn interpretation(mut self, interpretation: Interpretation) -> Self {
+        self.interpretations.push(interpretation);
+        self
+    }
+
+    pub fn disease(mut self, disease: Disease) -> Self {
+        self.diseases.push(disease);
+        self
+    }
+
+    pub fn medical_action(mut self, medical_action: MedicalAction) -> Self {
+        self.medical_actions.push(medical_action);
+        self
+    }
+
+    pub fn file(mut self, file: File) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    pub fn meta_data(mut self, meta_data: MetaData) -> Self {
+        self.meta_data = Some(meta_data);
+        self
+    }
+
+    /// Add a Resource to the MetaData of the phenopacket (the MetaData itself must be set with [`Self::meta_data`]).
+    pub fn resource(mut self, resource: Resource) -> Self {
+        self.resources.push(resource);
+        self
+    }
+
+    /// Create the Phenopacket. Returns an error if the id, the subject, or the MetaData is missing, or if the
+    /// `individual_id` of a biosample is set to an individual other than the subject. Biosamples without an
+    /// `individual_id` are taken from the subject, and their `individual_id` is set to the id of the subject.
+    pub fn build(mut self) -> Result<Phenopacket> {
+        if self.id.trim().is_empty() {
+            return Err(Error::missing_phenopacket_element("an id"));
+        }
+        let subject = self.subject.ok_or_else(|| Error::missing_phenopacket_element("a subject"))?;
+        for biosample in &mut self.biosamples {
+            if biosample.individual_id.is_empty() {
+                biosample.individual_id.clone_from(&subject.id);
+            } else if biosample.individual_id != subject.id {
+                return Err(Error::biosample_of_other_individual(biosample, &subject));
+            }
+        }
+        let mut meta_data = self.meta_data.ok_or_else(|| Error::missing_phenopacket_element("MetaData"))?;
+        meta_data.resources.extend(self.resources);
+        Ok(Phenopacket {
+            id: self.id,
+            subject: Some(subject),
+            phenotypic_features: self.phenotypic_features,
+            measurements: self.measurements,
+            biosamples: self.biosamples,
+            interpretations: self.interpretations,
+            diseases: self.diseases,
+            medical_actions: self.medical_actions,
+            files: self.files,
+            meta_data: Some(meta_data),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{biosample_builder::BiosampleBuilder, builder::Builder, resources::Resources};
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn individual() -> Individual {
+        Builder::individual("proband A")
+    }
+
+    #[fixture]
+    fn meta_data() -> MetaData {
+        Builder::meta_data_now("anonymous biocurator")
+    }
+
+    #[rstest]
+    fn test_build_complete_phenopacket(individual: Individual, meta_data: MetaData) -> Result<()> {
+        let vsd = Builder::ontology_class("HP:0001629", "Ventricular septal defect")?;
+        let bethlem = Builder::ontology_class("OMIM:158810", "Bethlem myopathy 1")?;
+        let phenopacket = PhenopacketBuilder::new("arbitrary proband id")
+            .subject(individual)
+            .meta_data(meta_data)
+            .phenotypic_feature(Builder::phenotypic_feature_observed(vsd))
+            .disease(Builder::disease(bethlem))
+            .resource(Resources::hpo_version("2025-03-03"))
+            .resource(Resources::omim_version("2022-11-23"))
+            .build()?;
+        assert_eq!("arbitrary proband id", phenopacket.id);
+        assert_eq!("proband A", phenopacket.subject.unwrap().id);
+        assert_eq!(1, phenopacket.phenotypic_features.len());
+        assert_eq!(1, phenopacket.diseases.len());
+        assert_eq!(2, phenopacket.meta_data.unwrap().resources.len());
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_missing_id(individual: Individual, meta_data: MetaData) {
+        let result = PhenopacketBuilder::new("").subject(individual).meta_data(meta_data).build();
+        assert!(matches!(&result, Err(Error::PhenopacketError { .. })));
+        assert_eq!("Cannot build Phenopacket without an id", result.unwrap_err().to_string());
+    }
+
+    #[rstest]
+    fn test_missing_subject(meta_data: MetaData) {
+        let result = PhenopacketBuilder::new("id").meta_data(meta_data).build();
+        assert_eq!("Cannot build Phenopacket without a subject", result.unwrap_err().to_string());
+    }
+
+    #[rstest]
+    fn test_biosample_of_subject(individual: Individual, meta_data: MetaData) -> Result<()> {
+        let biosample = BiosampleBuilder::new("sample 1").individual_id("proband A").build()?;
+        let phenopacket = PhenopacketBuilder::new("id")
+            .subject(individual.clone())
+            .meta_data(meta_data.clone())
+            .biosample(biosample)
+            .build()?;
+        assert_eq!(1, phenopacket.biosamples.len());
+        let other = BiosampleBuilder::new("sample 2").individual_id("sibling").build()?;
+        let result = PhenopacketBuilder::new("id").subject(individual).meta_data(meta_data).biosample(other).build();
+        assert_eq!(
+            "Biosample 'sample 2' is from individual 'sibling' but the subject is 'proband A'",
+            result.unwrap_err().to_string());
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_biosample_without_individual_id(individual: Individual, meta_data: MetaData) -> Result<()> {
+        let biosample = BiosampleBuilder::new("sample 1").build()?;
+        assert!(biosample.individual_id.is_empty());
+        let phenopacket = PhenopacketBuilder::new("id")
+            .subject(individual)
+            .meta_data(meta_data)
+            .biosample(biosample)
+            .build()?;
+        assert_eq!("proband A", phenopacket.biosamples[0].individual_id);
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_missing_meta_data(individual: Individual) {
+        let result = PhenopacketBuilder::new("id").subject(individual).build();
+        assert_eq!("Cannot build Phenopacket without MetaData", result.unwrap_err().to_string());
+    }
+}
@@ -0,0 +1,262 @@
+//! Registry of MetaData Resources indexed by namespace prefix
+//!
+//! The default registry contains all resources of the [`Resources`] catalogue. Additional resources
+//! (e.g., ORPHA, MAXO, GO) can be registered programmatically or loaded from a YAML or JSON file that
+//! contains a list of resources in the same format as `MetaData.resources` of a phenopacket, e.g.
+//!
+//! ```yaml
+//! - id: orpha
+//!   name: Orphanet Rare Disease Ontology
+//!   namespacePrefix: ORPHA
+//!   iriPrefix: "http://www.orpha.net/ORDO/Orphanet_"
+//!   url: "http://www.orpha.net/ordo"
+//!   version: "4.5"
+//! ```
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use phenopackets::schema::v2::core::Resource;
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::builders::builder::curie_prefix;
+use crate::builders::resources::{Resources, DEFAULT_CHEBI_VERSION, DEFAULT_UCUM_VERSION};
+use crate::error::{Error, Result};
+use crate::ontology_class_visitor::ontology_classes;
+
+
+lazy_static! {
+    /// Registry with the resources of the [`Resources`] catalogue.
+    pub static ref DEFAULT_RESOURCE_REGISTRY: ResourceRegistry = ResourceRegistry::default();
+}
+
+
+impl Error {
+    fn resource_file(path: &Path, reason: impl std::fmt::Display) -> Self {
+        Error::ResourceError { msg: format!("Could not load resources from {}: {reason}", path.display()) }
+    }
+}
+
+
+/// Resources indexed by their (case-insensitive) namespace prefix.
+#[derive(Debug, Clone)]
+pub struct ResourceRegistry {
+    resources: HashMap<String, Resource>,
+}
+
+impl Default for ResourceRegistry {
+    /// A registry with all resources of the [`Resources`] catalogue.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for resource in [
+            Resources::hpo_version(""),
+            Resources::geno_version(""),
+            Resources::pato_version(""),
+            Resources::efo_version(""),
+            Resources::eco_version(""),
+            Resources::cl_version(""),
+            Resources::ncit_version(""),
+            Resources::mondo_version(""),
+            Resources::uberon_version(""),
+            Resources::ncbi_taxon_version(""),
+            Resources::so_version(""),
+            Resources::hgnc_version(""),
+            Resources::uo_version(""),
+            Resources::ucum_version(DEFAULT_UCUM_VERSION),
+            Resources::loinc_version(""),
+            Resources::drug_central_version(""),
+            Resources::omim_version(""),
+            Resources::chebi_version(DEFAULT_CHEBI_VERSION),
+        ] {
+            registry.register(resource);
+        }
+        registry
+    }
+}
+
+impl ResourceRegistry {
+    /// A registry without any resources.
+    pub fn empty() -> Self {
+        ResourceRegistry { resources: HashMap::new() }
+    }
+
+    /// Create a registry with the resources of a YAML or JSON file (without the default catalogue).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let mut registry = Self::empty();
+        registry.load_file(path)?;
+        Ok(registry)
+    }
+
+    /// Add a resource, replacing any resource previously registered with the same prefix.
+    pub fn register(&mut self, resource: Resource) {
+        self.resources.insert(resource.namespace_prefix.to_lowercase(), resource);
+    }
+
+    /// Register all resources of a YAML (`.yaml`, `.yml`) or JSON (`.json`) file.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let is_json = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => true,
+            Some("yaml") | Some("yml") => false,
+            _ => return Err(Error::resource_file(path, "expected a .json, .yaml, or .yml file")),
+        };
+        let content = fs::read_to_string(path).map_err(|e| Error::resource_file(path, e))?;
+        let resources: Vec<Resource> = if is_json {
+            serde_json::from_str(&content).map_err(|e| Error::resource_file(path, e))?
+        } else {
+            serde_yaml::from_str(&content).map_err(|e| Error::resource_file(path, e))?
+        };
+        for resource in resources {
+            self.register(resource);
+        }
+        Ok(())
+    }
+
+    /// Get the resource for a namespace prefix (case-insensitive).
+    pub fn get(&self, prefix: &str) -> Option<&Resource> {
+        self.resources.get(&prefix.to_lowercase())
+    }
+
+    pub fn contains(&self, prefix: &str) -> bool {
+        self.get(prefix).is_some()
+    }
+
+    /// Get a copy of the resource for a namespace prefix with the given version.
+    pub fn resource_version(&self, prefix: &str, version: &str) -> Option<Resource> {
+        self.get(prefix).map(|r| Resource {
+            version: version.to_string(),
+            ..r.clone()
+        })
+    }
+
+    /// The namespace prefixes of all registered resources, sorted alphabetically.
+    pub fn prefixes(&self) -> Vec<&str> {
+        let mut prefixes: Vec<&str> = self.resources.values().map(|r| r.namespace_prefix.as_str()).collect();
+        prefixes.sort_unstable();
+        prefixes
+    }
+
+    /// Add a Resource to `MetaData.resources` for every CURIE prefix used in the phenopacket that is not yet declared.
+    ///
+    /// Prefixes are compared case-insensitively, so `HP` and `hp` share one resource. The version is taken from
+    /// `versions` (prefix -> version) or, if absent, from the registered resource. If any prefix cannot be
+    /// resolved (not registered or no version), an error listing all such prefixes is returned and the
+    /// phenopacket is left unchanged.
+    pub fn populate_resources(&self, phenopacket: &mut Phenopacket, versions: &HashMap<String, String>) -> Result<()> {
+        let used: BTreeSet<String> = ontology_classes(phenopacket)
+            .into_iter()
+            .filter_map(|(_, clz)| curie_prefix(&clz.id).map(str::to_string))
+            .collect();
+        let meta_data = phenopacket.meta_data.as_mut().ok_or_else(|| Error::ResourceError {
+            msg: "Cannot add resources to a Phenopacket without MetaData".to_string(),
+        })?;
+        let mut declared: HashSet<String> =
+            meta_data.resources.iter().map(|r| r.namespace_prefix.to_lowercase()).collect();
+        let mut new_resources = vec![];
+        let mut unresolved = vec![];
+        for prefix in used {
+            if !declared.insert(prefix.to_lowercase()) {
+                continue;
+            }
+            let resource = self.get(&prefix).and_then(|r| {
+                let version = versions
+                    .iter()
+                    .find_map(|(p, v)| p.eq_ignore_ascii_case(&prefix).then_some(v))
+                    .unwrap_or(&r.version);
+                (!version.is_empty()).then(|| Resource { version: version.clone(), ..r.clone() })
+            });
+            match resource {
+                Some(resource) => new_resources.push(resource),
+                None => unresolved.push(prefix),
+            }
+        }
+        if !unresolved.is_empty() {
+            return Err(Error::ResourceError {
+                msg: format!("Could not resolve resources for prefixes: {}", unresolved.join(", ")),
+            });
+        }
+        meta_data.resources.extend(new_resources);
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phenopackets::schema::v2::core::{MetaData, OntologyClass, PhenotypicFeature};
+    use rstest::rstest;
+    use std::io::Write;
+
+    #[rstest]
+    #[case("HP", "HP")]
+    #[case("hp", "HP")]
+    #[case("ncbitaxon", "NCBITaxon")]
+    #[case("DRUGCENTRAL", "DrugCentral")]
+    fn test_case_insensitive_lookup(#[case] prefix: &str, #[case] namespace_prefix: &str) {
+        let registry = ResourceRegistry::default();
+        let resource = registry.get(prefix).unwrap();
+        assert_eq!(namespace_prefix, resource.namespace_prefix);
+    }
+
+    #[rstest]
+    fn test_resource_version() {
+        let registry = ResourceRegistry::default();
+        let hpo = registry.resource_version("HP", "2025-03-03").unwrap();
+        assert_eq!(Resources::hpo_version("2025-03-03"), hpo);
+        assert_eq!(DEFAULT_UCUM_VERSION, registry.get("UCUM").unwrap().version);
+        assert!(registry.get("ORPHA").is_none());
+        assert_eq!(18, registry.prefixes().len());
+    }
+
+    #[rstest]
+    fn test_register() {
+        let mut registry = ResourceRegistry::default();
+        registry.register(Resource {
+            id: "maxo".to_string(),
+            name: "Medical Action Ontology".to_string(),
+            namespace_prefix: "MAXO".to_string(),
+            ..Default::default()
+        });
+        assert_eq!("Medical Action Ontology", registry.get("maxo").unwrap().name);
+    }
+
+    #[rstest]
+    #[case("resources.yaml", "- id: orpha\n  name: Orphanet Rare Disease Ontology\n  namespacePrefix: ORPHA\n  iriPrefix: \"http://www.orpha.net/ORDO/Orphanet_\"\n  url: \"http://www.orpha.net/ordo\"\n  version: \"4.5\"\n")]
+    #[case("resources.json", r#"[{"id": "orpha", "name": "Orphanet Rare Disease Ontology", "namespacePrefix": "ORPHA", "iriPrefix": "http://www.orpha.net/ORDO/Orphanet_", "url": "http://www.orpha.net/ordo", "version": "4.5"}]"#)]
+    fn test_load_file(#[case] file_name: &str, #[case] content: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        fs::File::create(&path).unwrap().write_all(content.as_bytes()).unwrap();
+        let registry = ResourceRegistry::from_file(&path).unwrap();
+        let orpha = registry.get("orpha").unwrap();
+        assert_eq!("ORPHA", orpha.namespace_prefix);
+        assert_eq!("http://www.orpha.net/ORDO/Orphanet_", orpha.iri_prefix);
+        assert_eq!("4.5", orpha.version);
+    }
+
+    #[rstest]
+    fn test_populate_resources_ignores_case() {
+        let mut pp = Phenopacket {
+            meta_data: Some(MetaData { resources: vec![Resources::hpo_version("2025-03-03")], ..Default::default() }),
+            ..Default::default()
+        };
+        for id in ["HP:0001629", "hp:0001631", "mondo:0007947", "MONDO:0007947"] {
+            let clz = OntologyClass { id: id.to_string(), label: String::new() };
+            pp.phenotypic_features.push(PhenotypicFeature { r#type: Some(clz), ..Default::default() });
+        }
+        let versions = HashMap::from([("Mondo".to_string(), "2024-01-03".to_string())]);
+        ResourceRegistry::default().populate_resources(&mut pp, &versions).unwrap();
+        let resources = &pp.meta_data.unwrap().resources;
+        let prefixes: Vec<(&str, &str)> =
+            resources.iter().map(|r| (r.namespace_prefix.as_str(), r.version.as_str())).collect();
+        assert_eq!(vec![("HP", "2025-03-03"), ("MONDO", "2024-01-03")], prefixes);
+    }
+
+    #[rstest]
+    fn test_load_file_with_unknown_extension() {
+        let result = ResourceRegistry::from_file("resources.txt");
+        assert!(matches!(result, Err(Error::ResourceError { .. })));
+    }
+}
@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
 use phenopackets::schema::v2::core::Resource;
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::builders::resource_registry::DEFAULT_RESOURCE_REGISTRY;
+use crate::error::Result;
 
-const DEFAULT_UCUM_VERSION: &str = "2.1";
-const DEFAULT_CHEBI_VERSION: &str = "241";
+pub(crate) const DEFAULT_UCUM_VERSION: &str = "2.1";
+pub(crate) const DEFAULT_CHEBI_VERSION: &str = "241";
 
 pub struct Resources;
 
@@ -237,12 +243,29 @@ impl Resources {
         res.version = version.to_string();
         res
     }
+
+    /// Look up the Resource for a namespace prefix (case-insensitive) in the catalogue of known resources.
+    pub fn for_prefix(prefix: &str, version: &str) -> Option<Resource> {
+        DEFAULT_RESOURCE_REGISTRY.resource_version(prefix, version)
+    }
+}
+
+
+/// Add a Resource to `MetaData.resources` for every CURIE prefix used in the phenopacket that is not yet declared.
+///
+/// The versions are taken from `versions` (prefix -> version); UCUM and CHEBI fall back to a default version.
+/// See [`crate::builders::resource_registry::ResourceRegistry::populate_resources`] to use custom resources.
+pub fn populate_resources(phenopacket: &mut Phenopacket, versions: &HashMap<String, String>) -> Result<()> {
+    DEFAULT_RESOURCE_REGISTRY.populate_resources(phenopacket, versions)
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets;
+    use crate::error::Error;
     use phenopackets::schema::v2::core::time_element;
     use rstest::rstest;
 
@@ -273,4 +296,38 @@ mod tests {
    
     }
 
-}
\ No newline at end of file
+
+    fn phenopacket_with_terms() -> Phenopacket {
+        let mut pp = test_phenopackets::phenopacket();
+        pp.phenotypic_features.push(Builder::phenotypic_feature_observed(test_phenopackets::vsd()));
+        pp.interpretations.push(test_phenopackets::bethlem_interpretation());
+        pp
+    }
+
+    #[rstest]
+    fn test_populate_resources() {
+        let mut pp = phenopacket_with_terms();
+        pp.meta_data.as_mut().unwrap().resources.push(Resources::hpo_version("2025-03-03"));
+        let versions: HashMap<String, String> = [("HP", "2000-01-01"), ("GENO", "2023-10-08"), ("OMIM", "2022-11-23")]
+            .into_iter()
+            .map(|(p, v)| (p.to_string(), v.to_string()))
+            .collect();
+        populate_resources(&mut pp, &versions).unwrap();
+        let resources = &pp.meta_data.unwrap().resources;
+        let prefixes: Vec<&str> = resources.iter().map(|r| r.namespace_prefix.as_str()).collect();
+        assert_eq!(vec!["HP", "GENO", "OMIM"], prefixes);
+        // the existing HPO resource is not replaced
+        assert_eq!("2025-03-03", resources[0].version);
+    }
+
+    #[rstest]
+    fn test_populate_resources_unresolved() {
+        let mut pp = phenopacket_with_terms();
+        let versions: HashMap<String, String> = HashMap::from([("HP".to_string(), "2025-03-03".to_string())]);
+        let result = populate_resources(&mut pp, &versions);
+        assert!(matches!(&result, Err(Error::ResourceError { .. })));
+        assert_eq!("Could not resolve resources for prefixes: GENO, OMIM", result.unwrap_err().to_string());
+        assert!(pp.meta_data.unwrap().resources.is_empty());
+    }
+
+}
@@ -0,0 +1,29 @@
+//! Phenopackets and parts of phenopackets for unit tests.
+use phenopackets::schema::v2::core::{Diagnosis, Interpretation, OntologyClass};
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::builders::builder::Builder;
+use crate::constants::allelic_state::AllelicState;
+
+/// A phenopacket with the id `id` and MetaData, but nothing else.
+pub(crate) fn phenopacket() -> Phenopacket {
+    Builder::phenopacket("id", Builder::meta_data_now("anonymous biocurator"))
+}
+
+pub(crate) fn vsd() -> OntologyClass {
+    Builder::ontology_class("HP:0001629", "Ventricular septal defect").unwrap()
+}
+
+pub(crate) fn bethlem_myopathy() -> OntologyClass {
+    Builder::ontology_class("OMIM:158810", "Bethlem myopathy 1").unwrap()
+}
+
+/// A solved interpretation with a Bethlem myopathy diagnosis caused by a heterozygous (GENO:0000135) variant.
+pub(crate) fn bethlem_interpretation() -> Interpretation {
+    let mut vd = Builder::variation_descriptor("var");
+    vd.allelic_state = Some(AllelicState::heterozygous());
+    let vi = Builder::variant_interpretation_pathogenic(vd);
+    let gi = Builder::genomic_interpretation_from_variant("id", Default::default(), vi);
+    let dx = Diagnosis { disease: Some(bethlem_myopathy()), genomic_interpretations: vec![gi] };
+    Builder::solved_interpretation("interpretation", dx)
+}
@@ -108,13 +108,13 @@ define_time_element!(
 
 /// Create a TimeElement to represent gestational age, e.g., 34w5d
 pub fn gestational_age(weeks: i32, days: i32) -> Result<TimeElement> {
-    if days < 0 || days > 7 {
+    if !(0..=7).contains(&days) {
         return Err(Error::invalid_days(days));
     }
     if weeks < 0 {
         return Err(Error::invalid_weeks(weeks));
     }
-    let ga = GestationalAge {weeks: weeks, days: days};
+    let ga = GestationalAge {weeks, days};
     Ok(TimeElement{element: Some(phenopackets::schema::v2::core::time_element::Element::GestationalAge(ga))})
 }
 
@@ -122,9 +122,9 @@ pub fn age(iso8601duration: impl Into<String>) -> Result<TimeElement> {
     let iso = iso8601duration.into();
     if  ISO8601_RE.is_match(&iso) {
         let age = Age{iso8601duration: iso.to_string()};
-        return Ok(TimeElement{element: Some(phenopackets::schema::v2::core::time_element::Element::Age(age))});
+        Ok(TimeElement{element: Some(phenopackets::schema::v2::core::time_element::Element::Age(age))})
     } else {
-        return Err(Error::invalid_iso8601(&iso));
+        Err(Error::invalid_iso8601(&iso))
     }
 }
 
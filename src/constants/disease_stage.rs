@@ -86,7 +86,7 @@ mod test {
 
     #[rstest]
 
-    fn test_stage_IV() {
+    fn test_stage_iv() {
         let stage4 = DiseaseStage::nyha_class_iv();
         let ncit_id = "NCIT:C66908";
         let ncit_label = "New York Heart Association Class IV"; 
@@ -1,5 +1,5 @@
 
-/// Constants for laterality of findings.
+//! Constants for laterality of findings.
 
 use phenopackets::schema::v2::core::OntologyClass;
 
@@ -98,4 +98,42 @@ impl Unit {
     pub fn enzyme_unit_per_liter() -> OntologyClass {
         OntologyClass { id: "UCUM:U.L-1".to_string(), label: "enzyme unit per liter".to_string() }
     }
+
+    /// Look up a unit by the name of its function (e.g., `mm_hg`) or by its id (e.g., `UCUM:mm[Hg]`).
+    pub fn from_name(name: &str) -> Option<OntologyClass> {
+        UNITS_BY_NAME
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, unit)| unit())
+            .or_else(|| UNITS_BY_NAME.iter().map(|(_, unit)| unit()).find(|u| u.id == name))
+    }
 }
+
+type UnitFn = fn() -> OntologyClass;
+
+const UNITS_BY_NAME: [(&str, UnitFn); 24] = [
+    ("degree", Unit::degree),
+    ("diopter", Unit::diopter),
+    ("gram", Unit::gram),
+    ("gram_per_kilogram", Unit::gram_per_kilogram),
+    ("kilogram", Unit::kilogram),
+    ("liter", Unit::liter),
+    ("meter", Unit::meter),
+    ("microgram", Unit::microgram),
+    ("microgram_per_deciliter", Unit::microgram_per_deciliter),
+    ("microgram_per_liter", Unit::microgram_per_liter),
+    ("microliter", Unit::microliter),
+    ("micrometer", Unit::micrometer),
+    ("milligram", Unit::milligram),
+    ("milligram_per_day", Unit::milligram_per_day),
+    ("milligram_per_deciliter", Unit::milligram_per_deciliter),
+    ("mg_per_kg", Unit::mg_per_kg),
+    ("milliliter", Unit::milliliter),
+    ("millimeter", Unit::millimeter),
+    ("mm_hg", Unit::mm_hg),
+    ("millimole", Unit::millimole),
+    ("mole", Unit::mole),
+    ("mole_per_liter", Unit::mole_per_liter),
+    ("mole_per_milliliter", Unit::mole_per_milliliter),
+    ("enzyme_unit_per_liter", Unit::enzyme_unit_per_liter),
+];
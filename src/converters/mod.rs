@@ -0,0 +1,2 @@
+//! Conversion between versions of the Phenopacket Schema
+pub mod v1_to_v2;
@@ -0,0 +1,555 @@
+//! Upgrade Phenopacket Schema v1 messages to v2
+//!
+//! Most v1 fields have a direct v2 counterpart. The main structural changes are
+//! * the onset and age oneofs (`age_of_onset`, `class_of_onset`, ...) become a [`TimeElement`]
+//! * the top-level `genes` and `variants` (and the variants of biosamples) move into an [`Interpretation`] as
+//!   [`GeneDescriptor`]s and VRSATILE [`VariationDescriptor`]s
+//! * `HtsFile` becomes [`File`], with the format, assembly, and description stored as file attributes
+//!
+//! Fields without a lossless mapping are listed in the [`ConversionReport`] returned together with the v2 packet.
+use std::collections::HashMap;
+use std::fmt;
+
+use phenopackets::ga4gh::vrsatile::v1::{Expression, GeneDescriptor, VariationDescriptor, VcfRecord};
+use phenopackets::schema::v1::core as v1;
+use phenopackets::schema::v1::Phenopacket as V1Phenopacket;
+use phenopackets::schema::v2::core::genomic_interpretation::InterpretationStatus;
+use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::{
+    AcmgPathogenicityClassification, Age, AgeRange, Biosample, Diagnosis, Disease, Evidence, ExternalReference,
+    File, GenomicInterpretation, Individual, Interpretation, MetaData, OntologyClass, PhenotypicFeature, Procedure,
+    Resource, TimeElement, Update,
+};
+use phenopackets::schema::v2::Phenopacket;
+use serde::{Deserialize, Serialize};
+
+use crate::builders::builder::{Builder, PHENOPACKET_SCHEMA_VERSION};
+use crate::builders::expressions::Expressions;
+
+
+/// A v1 field whose content could not be carried over to v2 without loss (or only approximately).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversionIssue {
+    /// Location of the field in the v1 phenopacket, e.g., `/biosamples/0/isControlSample`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConversionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+
+/// The fields of a v1 phenopacket that were dropped or changed in meaning during the upgrade.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversionReport {
+    pub issues: Vec<ConversionIssue>,
+}
+
+impl ConversionReport {
+    /// Return true if every v1 field was carried over to v2.
+    pub fn is_lossless(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn add(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ConversionIssue { path: path.into(), message: message.into() });
+    }
+}
+
+
+/// Convert a v1 phenopacket to v2.
+///
+/// The genes and variants of the packet and its biosamples are collected in a single [`Interpretation`]. If the
+/// v1 packet has exactly one disease, it is used as the diagnosis of the interpretation; otherwise the diagnosis
+/// has no disease and this is noted in the report.
+pub fn convert_phenopacket(phenopacket: &V1Phenopacket) -> (Phenopacket, ConversionReport) {
+    let mut converter = Converter::default();
+    let v2 = converter.phenopacket(phenopacket);
+    (v2, converter.report)
+}
+
+
+#[derive(Default)]
+struct Converter {
+    report: ConversionReport,
+}
+
+impl Converter {
+    fn phenopacket(&mut self, pp: &V1Phenopacket) -> Phenopacket {
+        let subject_id = pp.subject.as_ref().map(|s| s.id.clone()).unwrap_or_default();
+        let mut genomic_interpretations = vec![];
+        for (i, gene) in pp.genes.iter().enumerate() {
+            genomic_interpretations.push(Builder::genomic_interpretation_from_gene_descriptor(
+                subject_id.as_str(),
+                InterpretationStatus::UnknownStatus,
+                gene_descriptor(gene)));
+            self.unknown_status(format!("/genes/{i}"));
+        }
+        for (i, variant) in pp.variants.iter().enumerate() {
+            if let Some(gi) = self.genomic_interpretation(&subject_id, variant, &format!("/variants/{i}")) {
+                genomic_interpretations.push(gi);
+            }
+        }
+        let biosamples = pp
+            .biosamples
+            .iter()
+            .enumerate()
+            .map(|(i, b)| self.biosample(b, &format!("/biosamples/{i}"), &mut genomic_interpretations))
+            .collect();
+        let mut interpretations = vec![];
+        if !genomic_interpretations.is_empty() {
+            let disease = match pp.diseases.as_slice() {
+                [disease] => disease.term.as_ref().map(ontology_class),
+                _ => {
+                    self.report.add(
+                        "/diseases",
+                        format!("Genes and variants could not be assigned to a diagnosis because the packet has {} diseases", pp.diseases.len()));
+                    None
+                }
+            };
+            interpretations.push(Interpretation {
+                id: format!("{}-interpretation", pp.id),
+                progress_status: ProgressStatus::UnknownProgress.into(),
+                diagnosis: Some(Diagnosis { disease, genomic_interpretations }),
+                summary: String::default(),
+            });
+        }
+        Phenopacket {
+            id: pp.id.clone(),
+            subject: pp.subject.as_ref().map(|s| self.individual(s)),
+            phenotypic_features: pp.phenotypic_features.iter().map(phenotypic_feature).collect(),
+            measurements: vec![],
+            biosamples,
+            interpretations,
+            diseases: pp.diseases.iter().map(disease).collect(),
+            medical_actions: vec![],
+            files: pp.hts_files.iter().map(file).collect(),
+            meta_data: pp.meta_data.as_ref().map(meta_data),
+        }
+    }
+
+    fn individual(&mut self, individual: &v1::Individual) -> Individual {
+        let time_at_last_encounter = individual.age.as_ref().map(|age| {
+            self.report.add("/subject/age", "Mapped to timeAtLastEncounter, which may differ from the intended age");
+            match age {
+                v1::individual::Age::AgeAtCollection(a) => time_element_age(a),
+                v1::individual::Age::AgeRangeAtCollection(r) => time_element_age_range(r),
+            }
+        });
+        Individual {
+            id: individual.id.clone(),
+            alternate_ids: individual.alternate_ids.clone(),
+            date_of_birth: individual.date_of_birth,
+            time_at_last_encounter,
+            vital_status: None,
+            // the Sex and KaryotypicSex enums have the same values in v1 and v2
+            sex: individual.sex,
+            karyotypic_sex: individual.karyotypic_sex,
+            gender: None,
+            taxonomy: individual.taxonomy.as_ref().map(ontology_class),
+        }
+    }
+
+    fn biosample(
+        &mut self,
+        biosample: &v1::Biosample,
+        path: &str,
+        genomic_interpretations: &mut Vec<GenomicInterpretation>,
+    ) -> Biosample {
+        for (i, variant) in biosample.variants.iter().enumerate() {
+            let variant_path = format!("{path}/variants/{i}");
+            if let Some(gi) = self.genomic_interpretation(&biosample.id, variant, &variant_path) {
+                genomic_interpretations.push(gi);
+            }
+        }
+        if biosample.is_control_sample {
+            self.report.add(format!("{path}/isControlSample"), "Dropped (no v2 equivalent)");
+        }
+        Biosample {
+            id: biosample.id.clone(),
+            individual_id: biosample.individual_id.clone(),
+            description: biosample.description.clone(),
+            sampled_tissue: biosample.sampled_tissue.as_ref().map(ontology_class),
+            phenotypic_features: biosample.phenotypic_features.iter().map(phenotypic_feature).collect(),
+            taxonomy: biosample.taxonomy.as_ref().map(ontology_class),
+            time_of_collection: biosample.individual_age_at_collection.as_ref().map(|age| match age {
+                v1::biosample::IndividualAgeAtCollection::AgeOfIndividualAtCollection(a) => time_element_age(a),
+                v1::biosample::IndividualAgeAtCollection::AgeRangeOfIndividualAtCollection(r) => time_element_age_range(r),
+            }),
+            histological_diagnosis: biosample.histological_diagnosis.as_ref().map(ontology_class),
+            tumor_progression: biosample.tumor_progression.as_ref().map(ontology_class),
+            tumor_grade: biosample.tumor_grade.as_ref().map(ontology_class),
+            diagnostic_markers: biosample.diagnostic_markers.iter().map(ontology_class).collect(),
+            procedure: biosample.procedure.as_ref().map(|p| Procedure {
+                code: p.code.as_ref().map(ontology_class),
+                body_site: p.body_site.as_ref().map(ontology_class),
+                performed: None,
+            }),
+            files: biosample.hts_files.iter().map(file).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Wrap a v1 variant in a GenomicInterpretation of the subject or biosample. Returns None (and reports the
+    /// variant) if it has no allele.
+    fn genomic_interpretation(
+        &mut self,
+        subject_or_biosample_id: &str,
+        variant: &v1::Variant,
+        path: &str,
+    ) -> Option<GenomicInterpretation> {
+        let Some(allele) = variant.allele.as_ref() else {
+            self.report.add(path, "Dropped variant without allele");
+            return None;
+        };
+        let mut descriptor = match allele {
+            v1::variant::Allele::HgvsAllele(a) => {
+                let mut vd = Builder::variation_descriptor(a.id.as_str());
+                vd.expressions.push(hgvs_expression(&a.hgvs));
+                vd
+            }
+            v1::variant::Allele::VcfAllele(a) => {
+                if !a.vcf_version.is_empty() {
+                    self.report.add(format!("{path}/vcfAllele/vcfVersion"), "Dropped (no v2 equivalent)");
+                }
+                let pos = u64::try_from(a.pos).unwrap_or_else(|_| {
+                    self.report.add(format!("{path}/vcfAllele/pos"), format!("Invalid negative position {}", a.pos));
+                    0
+                });
+                let mut vd = Builder::variation_descriptor(a.id.as_str());
+                vd.vcf_record = Some(VcfRecord {
+                    id: a.id.clone(),
+                    info: a.info.clone(),
+                    ..Builder::vcf_record(a.genome_assembly.as_str(), a.chr.as_str(), pos, a.r#ref.as_str(), a.alt.as_str())
+                });
+                vd
+            }
+            v1::variant::Allele::SpdiAllele(a) => {
+                let mut vd = Builder::variation_descriptor(a.id.as_str());
+                vd.expressions.push(Expressions::spdi(format!(
+                    "{}:{}:{}:{}", a.seq_id, a.position, a.deleted_sequence, a.inserted_sequence)));
+                vd
+            }
+            v1::variant::Allele::IscnAllele(a) => {
+                let mut vd = Builder::variation_descriptor(a.id.as_str());
+                vd.expressions.push(Expressions::iscn(a.iscn.as_str()));
+                vd
+            }
+        };
+        if descriptor.id.is_empty() {
+            self.report.add(path, "Allele has no id; the VariationDescriptor id is left empty");
+        }
+        descriptor.allelic_state = variant.zygosity.as_ref().map(ontology_class);
+        self.unknown_status(path);
+        let vi = Builder::variant_interpretation_acmg(AcmgPathogenicityClassification::NotProvided, descriptor);
+        Some(Builder::genomic_interpretation_from_variant(
+            subject_or_biosample_id,
+            InterpretationStatus::UnknownStatus,
+            vi))
+    }
+
+    fn unknown_status(&mut self, path: impl Into<String>) {
+        self.report.add(path, "v1 does not record an interpretation status; set to UNKNOWN_STATUS");
+    }
+}
+
+
+fn ontology_class(clz: &v1::OntologyClass) -> OntologyClass {
+    OntologyClass { id: clz.id.clone(), label: clz.label.clone() }
+}
+
+fn age(age: &v1::Age) -> Age {
+    Age { iso8601duration: age.age.clone() }
+}
+
+fn time_element_age(a: &v1::Age) -> TimeElement {
+    TimeElement { element: Some(Element::Age(age(a))) }
+}
+
+fn time_element_age_range(range: &v1::AgeRange) -> TimeElement {
+    TimeElement {
+        element: Some(Element::AgeRange(AgeRange {
+            start: range.start.as_ref().map(age),
+            end: range.end.as_ref().map(age),
+        })),
+    }
+}
+
+fn time_element_class(clz: &v1::OntologyClass) -> TimeElement {
+    TimeElement { element: Some(Element::OntologyClass(ontology_class(clz))) }
+}
+
+fn phenotypic_feature(pf: &v1::PhenotypicFeature) -> PhenotypicFeature {
+    PhenotypicFeature {
+        description: pf.description.clone(),
+        r#type: pf.r#type.as_ref().map(ontology_class),
+        excluded: pf.negated,
+        severity: pf.severity.as_ref().map(ontology_class),
+        modifiers: pf.modifiers.iter().map(ontology_class).collect(),
+        onset: pf.onset.as_ref().map(|onset| match onset {
+            v1::phenotypic_feature::Onset::AgeOfOnset(a) => time_element_age(a),
+            v1::phenotypic_feature::Onset::AgeRangeOfOnset(r) => time_element_age_range(r),
+            v1::phenotypic_feature::Onset::ClassOfOnset(c) => time_element_class(c),
+        }),
+        resolution: None,
+        evidence: pf.evidence.iter().map(evidence).collect(),
+    }
+}
+
+fn disease(disease: &v1::Disease) -> Disease {
+    Disease {
+        term: disease.term.as_ref().map(ontology_class),
+        excluded: false,
+        onset: disease.onset.as_ref().map(|onset| match onset {
+            v1::disease::Onset::AgeOfOnset(a) => time_element_age(a),
+            v1::disease::Onset::AgeRangeOfOnset(r) => time_element_age_range(r),
+            v1::disease::Onset::ClassOfOnset(c) => time_element_class(c),
+        }),
+        resolution: None,
+        disease_stage: disease.disease_stage.iter().map(ontology_class).collect(),
+        clinical_tnm_finding: disease.tnm_finding.iter().map(ontology_class).collect(),
+        primary_site: None,
+        laterality: None,
+    }
+}
+
+fn external_reference(reference: &v1::ExternalReference) -> ExternalReference {
+    Builder::external_reference(reference.id.as_str(), reference.description.as_str())
+}
+
+fn evidence(evidence: &v1::Evidence) -> Evidence {
+    Evidence {
+        evidence_code: evidence.evidence_code.as_ref().map(ontology_class),
+        reference: evidence.reference.as_ref().map(external_reference),
+    }
+}
+
+fn gene_descriptor(gene: &v1::Gene) -> GeneDescriptor {
+    GeneDescriptor {
+        alternate_ids: gene.alternate_ids.clone(),
+        ..Builder::gene_descriptor(gene.id.as_str(), gene.symbol.as_str())
+    }
+}
+
+/// Choose the HGVS syntax from the reference sequence type of the expression (e.g., `NM_000138.4:c.6751G>A`).
+fn hgvs_expression(hgvs: &str) -> Expression {
+    let syntax = match hgvs.split_once(':').map(|(_, change)| change) {
+        Some(change) if change.starts_with("c.") => "hgvs.c",
+        Some(change) if change.starts_with("g.") => "hgvs.g",
+        Some(change) if change.starts_with("p.") => "hgvs.p",
+        _ => "hgvs",
+    };
+    Expression { syntax: syntax.to_string(), value: hgvs.to_string(), version: String::default() }
+}
+
+fn file(hts_file: &v1::HtsFile) -> File {
+    let mut file_attributes = HashMap::new();
+    let format = v1::hts_file::HtsFormat::try_from(hts_file.hts_format).unwrap_or(v1::hts_file::HtsFormat::Unknown);
+    file_attributes.insert("htsFormat".to_string(), format.as_str_name().to_string());
+    if !hts_file.genome_assembly.is_empty() {
+        file_attributes.insert("genomeAssembly".to_string(), hts_file.genome_assembly.clone());
+    }
+    if !hts_file.description.is_empty() {
+        file_attributes.insert("description".to_string(), hts_file.description.clone());
+    }
+    File {
+        uri: hts_file.uri.clone(),
+        individual_to_file_identifiers: hts_file.individual_to_sample_identifiers.clone(),
+        file_attributes,
+    }
+}
+
+/// Copy the v1 MetaData and update the schema version.
+fn meta_data(meta_data: &v1::MetaData) -> MetaData {
+    MetaData {
+        created: meta_data.created,
+        created_by: meta_data.created_by.clone(),
+        submitted_by: meta_data.submitted_by.clone(),
+        resources: meta_data
+            .resources
+            .iter()
+            .map(|r| Resource {
+                id: r.id.clone(),
+                name: r.name.clone(),
+                url: r.url.clone(),
+                version: r.version.clone(),
+                namespace_prefix: r.namespace_prefix.clone(),
+                iri_prefix: r.iri_prefix.clone(),
+            })
+            .collect(),
+        updates: meta_data
+            .updates
+            .iter()
+            .map(|u| Update { timestamp: u.timestamp, updated_by: u.updated_by.clone(), comment: u.comment.clone() })
+            .collect(),
+        phenopacket_schema_version: PHENOPACKET_SCHEMA_VERSION.to_string(),
+        external_references: meta_data.external_references.iter().map(external_reference).collect(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phenopackets::schema::v2::core::genomic_interpretation::Call;
+    use rstest::{fixture, rstest};
+
+    fn v1_class(id: &str, label: &str) -> v1::OntologyClass {
+        v1::OntologyClass { id: id.to_string(), label: label.to_string() }
+    }
+
+    fn v1_age(iso: &str) -> v1::Age {
+        v1::Age { age: iso.to_string() }
+    }
+
+    #[fixture]
+    fn v1_phenopacket() -> V1Phenopacket {
+        V1Phenopacket {
+            id: "PMID_123-proband".to_string(),
+            subject: Some(v1::Individual {
+                id: "proband".to_string(),
+                sex: v1::Sex::Female.into(),
+                ..Default::default()
+            }),
+            phenotypic_features: vec![
+                v1::PhenotypicFeature {
+                    r#type: Some(v1_class("HP:0001629", "Ventricular septal defect")),
+                    onset: Some(v1::phenotypic_feature::Onset::ClassOfOnset(v1_class("HP:0003577", "Congenital onset"))),
+                    ..Default::default()
+                },
+                v1::PhenotypicFeature {
+                    r#type: Some(v1_class("HP:0000518", "Cataract")),
+                    negated: true,
+                    ..Default::default()
+                },
+            ],
+            diseases: vec![v1::Disease {
+                term: Some(v1_class("OMIM:158810", "Bethlem myopathy 1")),
+                onset: Some(v1::disease::Onset::AgeOfOnset(v1_age("P3Y"))),
+                tnm_finding: vec![v1_class("NCIT:C48766", "pT2 Stage Finding")],
+                ..Default::default()
+            }],
+            variants: vec![v1::Variant {
+                zygosity: Some(v1_class("GENO:0000135", "heterozygous")),
+                allele: Some(v1::variant::Allele::HgvsAllele(v1::HgvsAllele {
+                    id: "var1".to_string(),
+                    hgvs: "NM_001848.2:c.877G>A".to_string(),
+                })),
+            }],
+            meta_data: Some(v1::MetaData {
+                created_by: "anonymous biocurator".to_string(),
+                phenopacket_schema_version: "1.0".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn test_convert_features_and_diseases(v1_phenopacket: V1Phenopacket) {
+        let (pp, _) = convert_phenopacket(&v1_phenopacket);
+        assert_eq!("PMID_123-proband", pp.id);
+        assert_eq!(2, pp.phenotypic_features.len());
+        let onset = pp.phenotypic_features[0].onset.as_ref().unwrap();
+        assert_eq!(Some(Element::OntologyClass(OntologyClass {
+            id: "HP:0003577".to_string(),
+            label: "Congenital onset".to_string(),
+        })), onset.element);
+        assert!(pp.phenotypic_features[1].excluded);
+        let disease = &pp.diseases[0];
+        assert_eq!(Some(Element::Age(Age { iso8601duration: "P3Y".to_string() })), disease.onset.as_ref().unwrap().element);
+        assert_eq!("NCIT:C48766", disease.clinical_tnm_finding[0].id);
+        assert_eq!(PHENOPACKET_SCHEMA_VERSION, pp.meta_data.unwrap().phenopacket_schema_version);
+    }
+
+    #[rstest]
+    fn test_convert_variant(v1_phenopacket: V1Phenopacket) {
+        let (pp, report) = convert_phenopacket(&v1_phenopacket);
+        assert_eq!(1, pp.interpretations.len());
+        let diagnosis = pp.interpretations[0].diagnosis.as_ref().unwrap();
+        assert_eq!("OMIM:158810", diagnosis.disease.as_ref().unwrap().id);
+        let gi = &diagnosis.genomic_interpretations[0];
+        assert_eq!("proband", gi.subject_or_biosample_id);
+        let Some(Call::VariantInterpretation(vi)) = &gi.call else {
+            panic!("expected a variant interpretation");
+        };
+        let vd = vi.variation_descriptor.as_ref().unwrap();
+        assert_eq!("var1", vd.id);
+        assert_eq!("hgvs.c", vd.expressions[0].syntax);
+        assert_eq!("GENO:0000135", vd.allelic_state.as_ref().unwrap().id);
+        assert_eq!(vec!["/variants/0"], report.issues.iter().map(|i| i.path.as_str()).collect::<Vec<_>>());
+    }
+
+    #[rstest]
+    fn test_convert_genes_without_single_disease(mut v1_phenopacket: V1Phenopacket) {
+        v1_phenopacket.variants.clear();
+        v1_phenopacket.diseases.clear();
+        v1_phenopacket.genes.push(v1::Gene {
+            id: "HGNC:2211".to_string(),
+            alternate_ids: vec!["ensembl:ENSG00000142156".to_string()],
+            symbol: "COL6A1".to_string(),
+        });
+        let (pp, report) = convert_phenopacket(&v1_phenopacket);
+        let diagnosis = pp.interpretations[0].diagnosis.as_ref().unwrap();
+        assert!(diagnosis.disease.is_none());
+        let Some(Call::Gene(gene)) = &diagnosis.genomic_interpretations[0].call else {
+            panic!("expected a gene descriptor");
+        };
+        assert_eq!("HGNC:2211", gene.value_id);
+        assert_eq!(vec!["ensembl:ENSG00000142156".to_string()], gene.alternate_ids);
+        assert!(report.issues.iter().any(|i| i.path == "/diseases"));
+    }
+
+    #[rstest]
+    fn test_convert_biosample(mut v1_phenopacket: V1Phenopacket) {
+        v1_phenopacket.variants.clear();
+        v1_phenopacket.biosamples.push(v1::Biosample {
+            id: "sample1".to_string(),
+            individual_id: "proband".to_string(),
+            sampled_tissue: Some(v1_class("UBERON:0002107", "liver")),
+            is_control_sample: true,
+            individual_age_at_collection: Some(
+                v1::biosample::IndividualAgeAtCollection::AgeOfIndividualAtCollection(v1_age("P5Y"))),
+            hts_files: vec![v1::HtsFile {
+                uri: "file:///data/sample1.vcf".to_string(),
+                hts_format: v1::hts_file::HtsFormat::Vcf.into(),
+                genome_assembly: "GRCh38".to_string(),
+                ..Default::default()
+            }],
+            variants: vec![v1::Variant {
+                zygosity: None,
+                allele: Some(v1::variant::Allele::VcfAllele(v1::VcfAllele {
+                    vcf_version: "VCFv4.2".to_string(),
+                    genome_assembly: "GRCh38".to_string(),
+                    id: "rs1".to_string(),
+                    chr: "21".to_string(),
+                    pos: 45989626,
+                    r#ref: "G".to_string(),
+                    alt: "A".to_string(),
+                    info: String::default(),
+                })),
+            }],
+            ..Default::default()
+        });
+        let (pp, report) = convert_phenopacket(&v1_phenopacket);
+        let biosample = &pp.biosamples[0];
+        assert_eq!(Some(Element::Age(Age { iso8601duration: "P5Y".to_string() })), biosample.time_of_collection.as_ref().unwrap().element);
+        assert_eq!(Some(&"VCF".to_string()), biosample.files[0].file_attributes.get("htsFormat"));
+        let gi = &pp.interpretations[0].diagnosis.as_ref().unwrap().genomic_interpretations[0];
+        assert_eq!("sample1", gi.subject_or_biosample_id);
+        let Some(Call::VariantInterpretation(vi)) = &gi.call else {
+            panic!("expected a variant interpretation");
+        };
+        assert_eq!(45989626, vi.variation_descriptor.as_ref().unwrap().vcf_record.as_ref().unwrap().pos);
+        let paths: Vec<&str> = report.issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(vec![
+            "/biosamples/0/variants/0/vcfAllele/vcfVersion",
+            "/biosamples/0/variants/0",
+            "/biosamples/0/isControlSample",
+        ], paths);
+    }
+}
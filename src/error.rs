@@ -20,6 +20,27 @@ pub enum Error {
     IndividualError {
         msg: String,
     },
+    IoError {
+        msg: String,
+    },
+    MeasurementError {
+        msg: String,
+    },
+    OntologyError {
+        msg: String,
+    },
+    /// A message could not be parsed. Line and column (1-based) are known for JSON and YAML input.
+    ParseError {
+        msg: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+    PhenopacketError {
+        msg: String,
+    },
+    ResourceError {
+        msg: String,
+    },
     TimeElementError {
         msg: String
     }
@@ -33,9 +54,16 @@ impl core::fmt::Display for Error {
         match self {
             Error::CurieError{msg} 
             | Error::GenomicInterpretationError{ msg}
+            | Error::IndividualError{msg}
+            | Error::IoError{msg}
+            | Error::MeasurementError{msg}
+            | Error::OntologyError{msg}
+            | Error::PhenopacketError{msg}
+            | Error::ResourceError{msg}
             | Error::TimeElementError{msg} => {
                 write!(fmt, "{msg}" )
             },
+            Error::ParseError { msg, .. } => write!(fmt, "{msg}"),
             _ => write!(fmt, "{self:?}"),
         }
     }
@@ -0,0 +1,493 @@
+//! Validate and convert directories of phenopacket files
+//!
+//! Files are processed in parallel. Each file is read with [`PhenopacketReader`], checked with the configured
+//! [`ValidationRunner`], and, if an output format is set, written to the output directory under the same
+//! relative path. The outcome of every file is collected in a [`BatchSummary`].
+//!
+//! Inputs that would be converted to the same file (e.g., `a.json` and `a.yaml`) keep their whole file name
+//! and get the extension of the output format appended (`a.json.json` and `a.yaml.json`).
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+use phenopackets::schema::v2::Phenopacket;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::io::json_printer::JsonPrinter;
+use crate::io::phenopacket_printer::PhenopacketPrinter;
+use crate::io::phenopacket_reader::{PhenopacketFormat, PhenopacketReader};
+use crate::io::protobuf_printer::ProtobufPrinter;
+use crate::io::yaml_printer::YamlPrinter;
+use crate::validation::phenopacket_validator::ValidationRunner;
+use crate::validation::validation_result::ValidationResult;
+
+/// Name of the summary file written to the output directory.
+pub const SUMMARY_FILE_NAME: &str = "batch-summary.json";
+
+
+/// The result of processing one file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileOutcome {
+    /// Path relative to the input directory.
+    pub path: PathBuf,
+    /// Path of the converted file, if any.
+    pub output: Option<PathBuf>,
+    /// The output was renamed because another input maps to the same output file.
+    pub renamed: bool,
+    pub validation_results: Vec<ValidationResult>,
+    /// Reading or writing error.
+    pub error: Option<String>,
+}
+
+impl FileOutcome {
+    /// The file was read (and written) and the validators reported no errors.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && !self.validation_results.iter().any(ValidationResult::is_error)
+    }
+}
+
+
+/// Outcomes of all processed files, in the (sorted) order of their paths.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchSummary {
+    pub outcomes: Vec<FileOutcome>,
+}
+
+impl BatchSummary {
+    pub fn succeeded(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.is_success()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.succeeded()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &FileOutcome> {
+        self.outcomes.iter().filter(|o| !o.is_success())
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Processed {} files: {} succeeded, {} failed",
+            self.outcomes.len(), self.succeeded(), self.failed())?;
+        for outcome in self.outcomes.iter().filter(|o| o.renamed) {
+            if let Some(output) = &outcome.output {
+                writeln!(f, "{} was written to {} to avoid a name clash", outcome.path.display(), output.display())?;
+            }
+        }
+        for outcome in self.failures() {
+            writeln!(f, "{}", outcome.path.display())?;
+            if let Some(error) = &outcome.error {
+                writeln!(f, "  {error}")?;
+            }
+            for result in outcome.validation_results.iter().filter(|r| r.is_error()) {
+                writeln!(f, "  {result}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Configuration of a batch run over a directory of phenopackets.
+///
+/// ```no_run
+/// use phenopacket_tools::io::batch::BatchProcessor;
+/// use phenopacket_tools::io::phenopacket_reader::PhenopacketFormat;
+/// use phenopacket_tools::validation::base_validator::BaseValidator;
+/// use phenopacket_tools::validation::phenopacket_validator::ValidationRunner;
+///
+/// let summary = BatchProcessor::new()
+///     .recursive(true)
+///     .include("*.json").unwrap()
+///     .validate_with(ValidationRunner::new().with_validator(BaseValidator))
+///     .convert_to(PhenopacketFormat::Yaml)
+///     .run("phenopackets", Some("out".as_ref()))
+///     .unwrap();
+/// println!("{summary}");
+/// ```
+#[derive(Default)]
+pub struct BatchProcessor {
+    recursive: bool,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    runner: Option<ValidationRunner>,
+    output_format: Option<PhenopacketFormat>,
+    threads: Option<usize>,
+}
+
+impl BatchProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also process the files in subdirectories.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Only process files whose path relative to the input directory matches one of the glob patterns
+    /// (e.g., `*.json`). Without include patterns, all files with a phenopacket extension are processed.
+    pub fn include(mut self, pattern: &str) -> Result<Self> {
+        self.include.push(Self::pattern(pattern)?);
+        Ok(self)
+    }
+
+    /// Skip files whose relative path matches the glob pattern.
+    pub fn exclude(mut self, pattern: &str) -> Result<Self> {
+        self.exclude.push(Self::pattern(pattern)?);
+        Ok(self)
+    }
+
+    fn pattern(pattern: &str) -> Result<Pattern> {
+        Pattern::new(pattern).map_err(|e| Error::io(format!("Invalid glob pattern '{pattern}': {e}")))
+    }
+
+    /// Validate every phenopacket with the runner.
+    pub fn validate_with(mut self, runner: ValidationRunner) -> Self {
+        self.runner = Some(runner);
+        self
+    }
+
+    /// Write every phenopacket to the output directory in this format.
+    pub fn convert_to(mut self, format: PhenopacketFormat) -> Self {
+        self.output_format = Some(format);
+        self
+    }
+
+    /// Number of worker threads (default: one per core).
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Find the files to process, as paths relative to the input directory, sorted.
+    pub fn find_files(&self, input_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        self.collect_files(input_dir, Path::new(""), &mut files)?;
+        files.sort();
+        Ok(files)
+    }
+
+    fn collect_files(&self, root: &Path, relative_dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        let dir = root.join(relative_dir);
+        let entries = fs::read_dir(&dir)
+            .map_err(|e| Error::io(format!("Could not read directory {}: {e}", dir.display())))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| Error::io(format!("Could not read directory {}: {e}", dir.display())))?;
+            let relative = relative_dir.join(entry.file_name());
+            let path = entry.path();
+            if path.is_dir() {
+                if self.recursive {
+                    self.collect_files(root, &relative, files)?;
+                }
+            } else if self.is_selected(&relative) {
+                files.push(relative);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_selected(&self, relative: &Path) -> bool {
+        if relative.file_name().is_some_and(|name| name == SUMMARY_FILE_NAME) {
+            return false;
+        }
+        let included = if self.include.is_empty() {
+            PhenopacketFormat::from_path(relative).is_some()
+        } else {
+            self.include.iter().any(|p| p.matches_path(relative))
+        };
+        included && !self.exclude.iter().any(|p| p.matches_path(relative))
+    }
+
+    /// Process all selected files of the input directory. Converted files and the summary
+    /// ([`SUMMARY_FILE_NAME`]) are written to the output directory, which is required for conversion. To
+    /// convert, the output directory must be outside of the input directory, so that no input is overwritten
+    /// and the converted files are not read as inputs of the next run.
+    pub fn run(&self, input_dir: impl AsRef<Path>, output_dir: Option<&Path>) -> Result<BatchSummary> {
+        let input_dir = input_dir.as_ref();
+        if self.output_format.is_some() {
+            let Some(output_dir) = output_dir else {
+                return Err(Error::io("An output directory is required to convert phenopackets"));
+            };
+            if resolve(output_dir)?.starts_with(resolve(input_dir)?) {
+                return Err(Error::io(format!("The output directory {} must not be inside the input directory {}",
+                    output_dir.display(), input_dir.display())));
+            }
+        }
+        let files = self.find_files(input_dir)?;
+        let outputs = self.output_paths(&files, output_dir);
+        let process_all = || -> Vec<FileOutcome> {
+            files
+                .par_iter()
+                .zip(&outputs)
+                .map(|(relative, output)| self.process(input_dir, relative, output))
+                .collect()
+        };
+        let outcomes = match self.threads {
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|e| Error::io(format!("Could not start worker threads: {e}")))?
+                .install(process_all),
+            None => process_all(),
+        };
+        let summary = BatchSummary { outcomes };
+        if let Some(output_dir) = output_dir {
+            fs::create_dir_all(output_dir)
+                .map_err(|e| Error::io(format!("Could not create {}: {e}", output_dir.display())))?;
+            let path = output_dir.join(SUMMARY_FILE_NAME);
+            let json = serde_json::to_string_pretty(&summary)
+                .map_err(|e| Error::io(format!("Could not serialize the summary: {e}")))?;
+            fs::write(&path, json).map_err(|e| Error::io(format!("Could not write {}: {e}", path.display())))?;
+        }
+        Ok(summary)
+    }
+
+    /// The output file of each input file. Inputs whose outputs clash are renamed; if the new names still clash
+    /// (e.g., `a.yaml` and `a.yaml.json` to JSON), these inputs are not written.
+    fn output_paths(&self, files: &[PathBuf], output_dir: Option<&Path>) -> Vec<Output> {
+        let (Some(format), Some(output_dir)) = (self.output_format, output_dir) else {
+            return files.iter().map(|_| Output::None).collect();
+        };
+        let paths: Vec<PathBuf> = files
+            .iter()
+            .map(|relative| output_dir.join(relative).with_extension(format.extension()))
+            .collect();
+        let counts = count_paths(&paths);
+        let paths: Vec<(PathBuf, bool)> = files
+            .iter()
+            .zip(paths)
+            .map(|(relative, path)| {
+                if counts[&path] > 1 {
+                    let mut name = relative.as_os_str().to_owned();
+                    name.push(".");
+                    name.push(format.extension());
+                    (output_dir.join(name), true)
+                } else {
+                    (path, false)
+                }
+            })
+            .collect();
+        let counts = count_paths(paths.iter().map(|(path, _)| path));
+        paths
+            .into_iter()
+            .map(|(path, renamed)| {
+                if counts[&path] > 1 {
+                    Output::Clash(path)
+                } else {
+                    Output::Path { path, renamed }
+                }
+            })
+            .collect()
+    }
+
+    fn process(&self, input_dir: &Path, relative: &Path, output: &Output) -> FileOutcome {
+        let mut outcome = FileOutcome {
+            path: relative.to_path_buf(),
+            output: None,
+            renamed: false,
+            validation_results: vec![],
+            error: None,
+        };
+        let phenopacket: Phenopacket = match PhenopacketReader::new().read_path(input_dir.join(relative)) {
+            Ok(pp) => pp,
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                return outcome;
+            }
+        };
+        if let Some(runner) = &self.runner {
+            outcome.validation_results = runner.validate(&phenopacket);
+        }
+        match (self.output_format, output) {
+            (Some(format), Output::Path { path, renamed }) => {
+                outcome.renamed = *renamed;
+                match write_phenopacket(&phenopacket, format, path) {
+                    Ok(()) => outcome.output = Some(path.clone()),
+                    Err(e) => outcome.error = Some(e.to_string()),
+                }
+            }
+            (_, Output::Clash(path)) => {
+                outcome.error = Some(format!("Not written: {} is also the output of another file", path.display()));
+            }
+            _ => {}
+        }
+        outcome
+    }
+}
+
+
+/// Where the converted phenopacket of an input file is written.
+enum Output {
+    None,
+    Path { path: PathBuf, renamed: bool },
+    /// The output file of more than one input.
+    Clash(PathBuf),
+}
+
+fn count_paths<'a>(paths: impl IntoIterator<Item = &'a PathBuf>) -> HashMap<PathBuf, usize> {
+    let mut counts = HashMap::new();
+    for path in paths {
+        *counts.entry(path.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+
+/// The absolute path with symbolic links resolved, also if the path (e.g., an output directory) does not exist yet.
+fn resolve(path: &Path) -> Result<PathBuf> {
+    let absolute = std::path::absolute(path)
+        .map_err(|e| Error::io(format!("Could not resolve {}: {e}", path.display())))?;
+    let mut missing = vec![];
+    let mut ancestor = absolute.as_path();
+    loop {
+        if let Ok(resolved) = fs::canonicalize(ancestor) {
+            return Ok(missing.iter().rev().fold(resolved, |path, name| path.join(name)));
+        }
+        match (ancestor.parent(), ancestor.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                ancestor = parent;
+            }
+            _ => return Ok(absolute.clone()),
+        }
+    }
+}
+
+fn write_phenopacket(phenopacket: &Phenopacket, format: PhenopacketFormat, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::io(format!("Could not create {}: {e}", parent.display())))?;
+    }
+    let result = match format {
+        PhenopacketFormat::Json => JsonPrinter::pretty().print_to_path(phenopacket, path),
+        PhenopacketFormat::Yaml => YamlPrinter.print_to_path(phenopacket, path),
+        PhenopacketFormat::Protobuf => ProtobufPrinter.print_to_path(phenopacket, path),
+    };
+    result.map_err(|e| Error::io(format!("Could not write {}: {e}", path.display())))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::validation::base_validator::BaseValidator;
+    use rstest::{fixture, rstest};
+    use tempfile::TempDir;
+
+    /// a.json, b.yaml, notes.txt, broken.json, sub/c.json (c has no id)
+    #[fixture]
+    fn input_dir() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let pp = |id: &str| Builder::phenopacket(id, Builder::meta_data_now("anonymous biocurator"));
+        JsonPrinter::pretty().print_to_path(&pp("a"), &dir.path().join("a.json")).unwrap();
+        YamlPrinter.print_to_path(&pp("b"), &dir.path().join("b.yaml")).unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a phenopacket").unwrap();
+        fs::write(dir.path().join("broken.json"), "{\"id\": ").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        JsonPrinter::pretty().print_to_path(&pp(""), &dir.path().join("sub").join("c.json")).unwrap();
+        dir
+    }
+
+    fn paths(files: &[PathBuf]) -> Vec<String> {
+        files.iter().map(|p| p.to_string_lossy().replace('\\', "/")).collect()
+    }
+
+    #[rstest]
+    fn test_find_files(input_dir: TempDir) {
+        let files = BatchProcessor::new().find_files(input_dir.path()).unwrap();
+        assert_eq!(vec!["a.json", "b.yaml", "broken.json"], paths(&files));
+        let processor = BatchProcessor::new().recursive(true).include("*.json").unwrap().exclude("broken*").unwrap();
+        let files = processor.find_files(input_dir.path()).unwrap();
+        assert_eq!(vec!["a.json", "sub/c.json"], paths(&files));
+    }
+
+    #[rstest]
+    fn test_validate_and_convert(input_dir: TempDir) {
+        let output_dir = tempfile::tempdir().unwrap();
+        let summary = BatchProcessor::new()
+            .recursive(true)
+            .validate_with(ValidationRunner::new().with_validator(BaseValidator))
+            .convert_to(PhenopacketFormat::Protobuf)
+            .threads(2)
+            .run(input_dir.path(), Some(output_dir.path()))
+            .unwrap();
+        assert_eq!(4, summary.outcomes.len());
+        assert_eq!(2, summary.succeeded());
+        let failed: Vec<PathBuf> = summary.failures().map(|o| o.path.clone()).collect();
+        assert_eq!(vec!["broken.json", "sub/c.json"], paths(&failed));
+        assert!(output_dir.path().join("b.pb").is_file());
+        assert!(output_dir.path().join("sub").join("c.pb").is_file());
+        assert!(output_dir.path().join(SUMMARY_FILE_NAME).is_file());
+        let pp: Phenopacket = PhenopacketReader::new().read_path(output_dir.path().join("a.pb")).unwrap();
+        assert_eq!("a", pp.id);
+    }
+
+    #[rstest]
+    fn test_same_stem_is_renamed(input_dir: TempDir) {
+        let pp = Builder::phenopacket("a2", Builder::meta_data_now("anonymous biocurator"));
+        YamlPrinter.print_to_path(&pp, &input_dir.path().join("a.yaml")).unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let summary = BatchProcessor::new()
+            .convert_to(PhenopacketFormat::Json)
+            .run(input_dir.path(), Some(output_dir.path()))
+            .unwrap();
+        let renamed: Vec<PathBuf> = summary.outcomes.iter().filter(|o| o.renamed).map(|o| o.path.clone()).collect();
+        assert_eq!(vec!["a.json", "a.yaml"], paths(&renamed));
+        assert!(!output_dir.path().join("a.json").exists());
+        let read = |name: &str| -> Phenopacket { PhenopacketReader::new().read_path(output_dir.path().join(name)).unwrap() };
+        assert_eq!("a", read("a.json.json").id);
+        assert_eq!("a2", read("a.yaml.json").id);
+        assert_eq!("b", read("b.json").id);
+    }
+
+    #[rstest]
+    fn test_clash_after_renaming_is_an_error(input_dir: TempDir) {
+        for name in ["a.yaml", "a.yaml.json"] {
+            fs::copy(input_dir.path().join("a.json"), input_dir.path().join(name)).unwrap();
+        }
+        let output_dir = tempfile::tempdir().unwrap();
+        let summary = BatchProcessor::new()
+            .include("a.*").unwrap()
+            .convert_to(PhenopacketFormat::Json)
+            .run(input_dir.path(), Some(output_dir.path()))
+            .unwrap();
+        let failed: Vec<PathBuf> = summary.failures().map(|o| o.path.clone()).collect();
+        assert_eq!(vec!["a.yaml", "a.yaml.json"], paths(&failed));
+        assert!(output_dir.path().join("a.json.json").is_file());
+    }
+
+    #[rstest]
+    fn test_summary_is_not_an_input(input_dir: TempDir) {
+        let processor = BatchProcessor::new().recursive(true);
+        processor.run(input_dir.path(), Some(&input_dir.path().join("sub"))).unwrap();
+        assert!(input_dir.path().join("sub").join(SUMMARY_FILE_NAME).is_file());
+        let files = processor.find_files(input_dir.path()).unwrap();
+        assert_eq!(vec!["a.json", "b.yaml", "broken.json", "sub/c.json"], paths(&files));
+    }
+
+    #[rstest]
+    #[case(".")]
+    #[case("sub")]
+    #[case("new/out")]
+    fn test_output_dir_inside_input_dir(input_dir: TempDir, #[case] output: &str) {
+        let result = BatchProcessor::new()
+            .convert_to(PhenopacketFormat::Json)
+            .run(input_dir.path(), Some(&input_dir.path().join(output)));
+        assert!(matches!(result, Err(Error::IoError { .. })));
+        assert!(!input_dir.path().join("b.json").exists());
+    }
+
+    #[rstest]
+    fn test_conversion_requires_output_dir(input_dir: TempDir) {
+        let result = BatchProcessor::new().convert_to(PhenopacketFormat::Json).run(input_dir.path(), None);
+        assert!(matches!(result, Err(Error::IoError { .. })));
+    }
+}
@@ -0,0 +1,199 @@
+use std::io::{self, Write};
+
+use prost::Message;
+use serde::Serialize;
+use serde_json::ser::{Formatter, Serializer};
+
+use crate::io::phenopacket_printer::PhenopacketPrinter;
+use crate::io::proto_json;
+
+/// Characters that the reference Java implementation (protobuf `JsonFormat`) escapes in strings.
+const ESCAPED_CHARS: [(char, &str); 7] = [
+    ('<', "\\u003c"),
+    ('>', "\\u003e"),
+    ('&', "\\u0026"),
+    ('=', "\\u003d"),
+    ('\'', "\\u0027"),
+    ('\u{2028}', "\\u2028"),
+    ('\u{2029}', "\\u2029"),
+];
+
+
+/// Print messages in the canonical proto3 JSON mapping (see [`crate::io::proto_json`]), e.g., with camelCase
+/// field names and without fields that have default values.
+///
+/// The output follows the Java phenopacket-tools (protobuf `JsonFormat`): characters such as `=` are escaped, and
+/// pretty output keeps the brackets of arrays on the lines of their elements (`[{`, `}, {`, and `}]`) and arrays of
+/// scalars on one line.
+#[derive(Debug, Clone)]
+pub struct JsonPrinter {
+    pretty: bool,
+}
+
+impl Default for JsonPrinter {
+    fn default() -> Self {
+        Self::pretty()
+    }
+}
+
+impl JsonPrinter {
+    /// Print indented JSON.
+    pub fn pretty() -> Self {
+        JsonPrinter { pretty: true }
+    }
+
+    /// Print JSON without insignificant whitespace, e.g., one message per line.
+    pub fn compact() -> Self {
+        JsonPrinter { pretty: false }
+    }
+
+    /// Print the message to a String.
+    pub fn to_string<M: Serialize>(&self, message: &M) -> io::Result<String> {
+        let value = proto_json::to_value(message)?;
+        let json = if self.pretty {
+            let mut buffer = vec![];
+            value.serialize(&mut Serializer::with_formatter(&mut buffer, JsonFormatFormatter::default()))?;
+            String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        } else {
+            serde_json::to_string(&value)?
+        };
+        Ok(escape_like_java(json))
+    }
+}
+
+impl PhenopacketPrinter for JsonPrinter {
+    fn print<M, W>(&self, message: &M, writer: &mut W) -> io::Result<()>
+    where
+        M: Message + Serialize,
+        W: Write,
+    {
+        writer.write_all(self.to_string(message)?.as_bytes())
+    }
+}
+
+
+/// Indents objects by two spaces like `JsonFormat`, but writes arrays inline: `"a": [{` ... `}, {` ... `}]`.
+#[derive(Debug, Default)]
+struct JsonFormatFormatter {
+    indent: usize,
+}
+
+impl JsonFormatFormatter {
+    fn new_line<W: ?Sized + Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b"\n")?;
+        (0..self.indent).try_for_each(|_| writer.write_all(b"  "))
+    }
+}
+
+impl Formatter for JsonFormatFormatter {
+    fn begin_array_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        if first { Ok(()) } else { writer.write_all(b", ") }
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.indent += 1;
+        writer.write_all(b"{")
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.indent -= 1;
+        self.new_line(writer)?;
+        writer.write_all(b"}")
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        if !first {
+            writer.write_all(b",")?;
+        }
+        self.new_line(writer)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+}
+
+
+/// The characters in [`ESCAPED_CHARS`] cannot occur outside of JSON strings, so they can be replaced in the
+/// serialized text.
+fn escape_like_java(json: String) -> String {
+    if !json.contains(|c| ESCAPED_CHARS.iter().any(|(e, _)| *e == c)) {
+        return json;
+    }
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match ESCAPED_CHARS.iter().find(|(e, _)| *e == c) {
+            Some((_, replacement)) => escaped.push_str(replacement),
+            None => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use phenopackets::schema::v2::Phenopacket;
+    use prost_types::Timestamp;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn phenopacket() -> Phenopacket {
+        let created = Timestamp { seconds: 1620988500, nanos: 0 };
+        let mut pp = Builder::phenopacket("PMID:1", Builder::meta_data(created, "curator <a&b>"));
+        let mut subject = Builder::individual("proband");
+        subject.alternate_ids = vec!["PMID:1-II-1".to_string(), "family:1".to_string()];
+        pp.subject = Some(subject);
+        let asd = Builder::ontology_class("HP:0001631", "Atrial septal defect").unwrap();
+        pp.phenotypic_features.push(Builder::phenotypic_feature_excluded(asd));
+        let vsd = Builder::ontology_class("HP:0001629", "Ventricular septal defect").unwrap();
+        pp.phenotypic_features.push(Builder::phenotypic_feature_observed(vsd));
+        pp
+    }
+
+    #[rstest]
+    fn test_compact(phenopacket: Phenopacket) {
+        let json = JsonPrinter::compact().to_string(&phenopacket).unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"id":"PMID:1","subject":{"id":"proband","alternateIds":["PMID:1-II-1","family:1"]},"#,
+                r#""phenotypicFeatures":[{"type":{"id":"HP:0001631","label":"Atrial septal defect"},"excluded":true},"#,
+                r#"{"type":{"id":"HP:0001629","label":"Ventricular septal defect"}}],"#,
+                r#""metaData":{"created":"2021-05-14T10:35:00Z","createdBy":"curator \u003ca\u0026b\u003e","phenopacketSchemaVersion":"2.0.2"}}"#),
+            json);
+    }
+
+    #[rstest]
+    fn test_pretty(phenopacket: Phenopacket) {
+        let mut buffer = vec![];
+        JsonPrinter::pretty().print(&phenopacket, &mut buffer).unwrap();
+        let json = String::from_utf8(buffer).unwrap();
+        let expected = r#"{
+  "id": "PMID:1",
+  "subject": {
+    "id": "proband",
+    "alternateIds": ["PMID:1-II-1", "family:1"]
+  },
+  "phenotypicFeatures": [{
+    "type": {
+      "id": "HP:0001631",
+      "label": "Atrial septal defect"
+    },
+    "excluded": true
+  }, {
+    "type": {
+      "id": "HP:0001629",
+      "label": "Ventricular septal defect"
+    }
+  }],
+  "metaData": {
+    "created": "2021-05-14T10:35:00Z",
+    "createdBy": "curator \u003ca\u0026b\u003e",
+    "phenopacketSchemaVersion": "2.0.2"
+  }
+}"#;
+        assert_eq!(expected, json);
+    }
+}
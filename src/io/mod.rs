@@ -1,2 +1,9 @@
 //! Serialize/Deserialize GA4GH phenopackets
-pub mod phenopacket_printer;
\ No newline at end of file
+pub mod batch;
+pub mod json_printer;
+pub mod ndjson;
+pub mod phenopacket_printer;
+pub mod phenopacket_reader;
+pub mod proto_json;
+pub mod protobuf_printer;
+pub mod yaml_printer;
@@ -0,0 +1,216 @@
+//! Stream phenopackets as newline-delimited JSON (NDJSON), one compact phenopacket per line
+//!
+//! Neither the reader nor the writer holds more than one phenopacket in memory. Gzip-compressed input is
+//! detected from its content; output is compressed with [`NdjsonWriter::gzip`] or a `.gz` file name.
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Lines, Read, Write};
+use std::path::Path;
+
+use flate2::bufread::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use phenopackets::schema::v2::Phenopacket;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::io::json_printer::JsonPrinter;
+use crate::io::proto_json;
+
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+
+/// Iterate over the phenopackets of an NDJSON stream. Blank lines are skipped.
+///
+/// ```no_run
+/// use phenopacket_tools::io::ndjson::NdjsonReader;
+///
+/// for phenopacket in NdjsonReader::open("cohort.ndjson.gz").unwrap() {
+///     println!("{}", phenopacket.unwrap().id);
+/// }
+/// ```
+pub struct NdjsonReader<'a> {
+    lines: Lines<Box<dyn BufRead + 'a>>,
+    line_number: usize,
+}
+
+impl<'a> NdjsonReader<'a> {
+    /// Read from a (plain or gzip-compressed) stream.
+    pub fn new<R: Read + 'a>(reader: R) -> Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let is_gzip = reader
+            .fill_buf()
+            .map_err(|e| Error::io(format!("Could not read input: {e}")))?
+            .starts_with(&GZIP_MAGIC_BYTES);
+        let reader: Box<dyn BufRead + 'a> = if is_gzip {
+            Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+        } else {
+            Box::new(reader)
+        };
+        Ok(NdjsonReader { lines: reader.lines(), line_number: 0 })
+    }
+
+    /// Read from a (plain or gzip-compressed) file.
+    pub fn open(path: impl AsRef<Path>) -> Result<NdjsonReader<'static>> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::io(format!("Could not open {}: {e}", path.display())))?;
+        NdjsonReader::new(file)
+    }
+
+    fn parse(&self, line: &str) -> Result<Phenopacket> {
+        let parse_error = |e: serde_json::Error, column| Error::ParseError {
+            msg: format!("Could not parse phenopacket on line {}: {e}", self.line_number),
+            line: Some(self.line_number),
+            column,
+        };
+        let value: Value = serde_json::from_str(line).map_err(|e| {
+            let column = e.column();
+            parse_error(e, Some(column))
+        })?;
+        proto_json::from_value(value).map_err(|e| parse_error(e, None))
+    }
+}
+
+impl Iterator for NdjsonReader<'_> {
+    type Item = Result<Phenopacket>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line_number += 1;
+            match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(self.parse(&line)),
+                Err(e) => {
+                    return Some(Err(Error::io(format!("Could not read line {}: {e}", self.line_number))));
+                }
+            }
+        }
+    }
+}
+
+
+enum Sink<W: Write> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+}
+
+/// Write phenopackets as NDJSON, in the same proto3 JSON mapping as [`JsonPrinter`].
+///
+/// Call [`NdjsonWriter::finish`] at the end, so that the gzip trailer is written and errors are reported.
+pub struct NdjsonWriter<W: Write> {
+    sink: Sink<W>,
+    printer: JsonPrinter,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    /// Write uncompressed NDJSON.
+    pub fn new(writer: W) -> Self {
+        NdjsonWriter { sink: Sink::Plain(writer), printer: JsonPrinter::compact() }
+    }
+
+    /// Write gzip-compressed NDJSON.
+    pub fn gzip(writer: W) -> Self {
+        NdjsonWriter { sink: Sink::Gzip(GzEncoder::new(writer, Compression::default())), printer: JsonPrinter::compact() }
+    }
+
+    /// Write one phenopacket as a line.
+    pub fn write(&mut self, phenopacket: &Phenopacket) -> Result<()> {
+        let mut line = self.printer.to_string(phenopacket)
+            .map_err(|e| Error::io(format!("Could not serialize phenopacket {}: {e}", phenopacket.id)))?;
+        line.push('\n');
+        let result = match &mut self.sink {
+            Sink::Plain(w) => w.write_all(line.as_bytes()),
+            Sink::Gzip(w) => w.write_all(line.as_bytes()),
+        };
+        result.map_err(|e| Error::io(format!("Could not write phenopacket {}: {e}", phenopacket.id)))
+    }
+
+    /// Write all phenopackets of an iterator, e.g., of an [`NdjsonReader`] after a transformation.
+    pub fn write_all<'p>(&mut self, phenopackets: impl IntoIterator<Item = &'p Phenopacket>) -> Result<()> {
+        phenopackets.into_iter().try_for_each(|pp| self.write(pp))
+    }
+
+    /// Flush the output (and finish the gzip stream) and return the underlying writer.
+    pub fn finish(self) -> Result<W> {
+        let result = match self.sink {
+            Sink::Plain(mut w) => w.flush().map(|_| w),
+            Sink::Gzip(w) => w.finish().and_then(|mut w| w.flush().map(|_| w)),
+        };
+        result.map_err(|e| Error::io(format!("Could not finish writing: {e}")))
+    }
+}
+
+impl NdjsonWriter<BufWriter<File>> {
+    /// Create the file; the output is gzip-compressed if the file name ends with `.gz`.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| Error::io(format!("Could not create {}: {e}", path.display())))?;
+        let writer = BufWriter::new(file);
+        if path.extension().is_some_and(|e| e == "gz") {
+            Ok(NdjsonWriter::gzip(writer))
+        } else {
+            Ok(NdjsonWriter::new(writer))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn phenopackets() -> Vec<Phenopacket> {
+        (1..=3)
+            .map(|i| {
+                let mut pp = Builder::phenopacket(format!("pp{i}"), Builder::meta_data_now("anonymous biocurator"));
+                pp.subject = Some(Builder::individual(format!("individual {i}")));
+                pp
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_round_trip(phenopackets: Vec<Phenopacket>, #[case] gzip: bool) {
+        let mut writer = if gzip { NdjsonWriter::gzip(vec![]) } else { NdjsonWriter::new(vec![]) };
+        writer.write_all(&phenopackets).unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_eq!(gzip, bytes.starts_with(&GZIP_MAGIC_BYTES));
+        let read: Vec<Phenopacket> = NdjsonReader::new(bytes.as_slice()).unwrap().map(Result::unwrap).collect();
+        assert_eq!(phenopackets, read);
+    }
+
+    #[rstest]
+    fn test_one_line_per_phenopacket(phenopackets: Vec<Phenopacket>) {
+        let mut writer = NdjsonWriter::new(vec![]);
+        writer.write_all(&phenopackets).unwrap();
+        let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(3, text.lines().count());
+        assert!(text.starts_with("{\"id\":\"pp1\",\"subject\":{\"id\":\"individual 1\"},"));
+    }
+
+    #[rstest]
+    fn test_errors_report_line_numbers() {
+        let input = "{\"id\": \"pp1\"}\n\n{\"id\": \n{\"id\": \"pp3\", \"subject\": 1}\n";
+        let results: Vec<Result<Phenopacket>> = NdjsonReader::new(input.as_bytes()).unwrap().collect();
+        assert_eq!(3, results.len());
+        assert_eq!("pp1", results[0].as_ref().unwrap().id);
+        assert!(matches!(results[1], Err(Error::ParseError { line: Some(3), .. })));
+        assert!(matches!(results[2], Err(Error::ParseError { line: Some(4), .. })));
+    }
+
+    #[rstest]
+    fn test_files(phenopackets: Vec<Phenopacket>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cohort.ndjson.gz");
+        let mut writer = NdjsonWriter::create(&path).unwrap();
+        writer.write_all(&phenopackets).unwrap();
+        writer.finish().unwrap();
+        let ids: Vec<String> = NdjsonReader::open(&path).unwrap().map(|pp| pp.unwrap().id).collect();
+        assert_eq!(vec!["pp1", "pp2", "pp3"], ids);
+    }
+}
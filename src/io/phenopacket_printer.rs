@@ -1,16 +1,24 @@
 use prost::Message;
+use serde::Serialize;
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 use std::path::Path;
 
 /// A trait for serializing Phenopacket (or similar) Protobuf messages to an output stream.
 pub trait PhenopacketPrinter {
-    /// Print the Protobuf message to a writer (e.g. file, stdout) in JSON or YAML.
-    fn print<W: Write>(&self, message: &dyn Message, writer: &mut W) -> io::Result<()>;
+    /// Print the Protobuf message to a writer (e.g. file, stdout) in JSON, YAML, or the protobuf wire format.
+    fn print<M, W>(&self, message: &M, writer: &mut W) -> io::Result<()>
+    where
+        M: Message + Serialize,
+        W: Write;
 
     /// Convenience method to print to a file path.
-    fn print_to_path(&self, message: &dyn Message, path: &Path) -> io::Result<()> {
-        let mut file = File::create(path)?;
-        self.print(message, &mut file)
+    fn print_to_path<M>(&self, message: &M, path: &Path) -> io::Result<()>
+    where
+        M: Message + Serialize,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.print(message, &mut writer)?;
+        writer.flush()
     }
 }
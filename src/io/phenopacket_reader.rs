@@ -0,0 +1,281 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use prost::Message;
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+use crate::io::proto_json;
+
+
+impl Error {
+    pub(crate) fn io(msg: impl Into<String>) -> Self {
+        Error::IoError { msg: msg.into() }
+    }
+}
+
+
+/// The serialization formats of phenopackets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhenopacketFormat {
+    Json,
+    Yaml,
+    /// Binary protobuf wire format, optionally length-delimited.
+    Protobuf,
+}
+
+impl fmt::Display for PhenopacketFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhenopacketFormat::Json => write!(f, "JSON"),
+            PhenopacketFormat::Yaml => write!(f, "YAML"),
+            PhenopacketFormat::Protobuf => write!(f, "protobuf"),
+        }
+    }
+}
+
+impl PhenopacketFormat {
+    /// Determine the format from the file extension (`.json`, `.yaml`/`.yml`, `.pb`/`.binpb`/`.bin`).
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(PhenopacketFormat::Json),
+            "yaml" | "yml" => Some(PhenopacketFormat::Yaml),
+            "pb" | "binpb" | "bin" => Some(PhenopacketFormat::Protobuf),
+            _ => None,
+        }
+    }
+
+    /// The file extension used when writing this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PhenopacketFormat::Json => "json",
+            PhenopacketFormat::Yaml => "yaml",
+            PhenopacketFormat::Protobuf => "pb",
+        }
+    }
+
+    /// Guess the format from the content. Content that is not UTF-8 text or that contains control characters
+    /// other than whitespace is taken to be protobuf; text starting with `{` is JSON and any other text YAML.
+    pub fn detect(content: &[u8]) -> Self {
+        let Ok(text) = std::str::from_utf8(content) else {
+            return PhenopacketFormat::Protobuf;
+        };
+        if text.chars().any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r')) {
+            PhenopacketFormat::Protobuf
+        } else if text.trim_start().starts_with('{') {
+            PhenopacketFormat::Json
+        } else {
+            PhenopacketFormat::Yaml
+        }
+    }
+}
+
+
+/// Read Phenopacket, Family, or Cohort messages from JSON, YAML, or binary protobuf.
+/// JSON and YAML are parsed according to the proto3 JSON mapping (see [`crate::io::proto_json`]).
+///
+/// Unless a format is set with [`PhenopacketReader::with_format`], it is determined from the file extension
+/// or, failing that, from the content.
+///
+/// ```no_run
+/// use phenopacket_tools::io::phenopacket_reader::PhenopacketReader;
+/// use phenopackets::schema::v2::{Family, Phenopacket};
+///
+/// let reader = PhenopacketReader::new();
+/// let phenopacket: Phenopacket = reader.read_path("PMID_30808312-proband.json").unwrap();
+/// let family: Family = reader.read_path("family.pb").unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhenopacketReader {
+    format: Option<PhenopacketFormat>,
+}
+
+impl PhenopacketReader {
+    /// A reader that detects the format of its input.
+    pub fn new() -> Self {
+        PhenopacketReader { format: None }
+    }
+
+    /// A reader for input in the given format.
+    pub fn with_format(format: PhenopacketFormat) -> Self {
+        PhenopacketReader { format: Some(format) }
+    }
+
+    /// Read a message from a file.
+    pub fn read_path<M>(&self, path: impl AsRef<Path>) -> Result<M>
+    where
+        M: Message + DeserializeOwned + Default,
+    {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::io(format!("Could not open {}: {e}", path.display())))?;
+        let format = self.format.or_else(|| PhenopacketFormat::from_path(path));
+        Self::read_with_format(BufReader::new(file), format).map_err(|e| match e {
+            Error::ParseError { msg, line, column } => Error::ParseError {
+                msg: format!("{}: {msg}", path.display()),
+                line,
+                column,
+            },
+            e => e,
+        })
+    }
+
+    /// Read a message from a reader, e.g., stdin.
+    pub fn read<M, R>(&self, reader: R) -> Result<M>
+    where
+        M: Message + DeserializeOwned + Default,
+        R: Read,
+    {
+        Self::read_with_format(reader, self.format)
+    }
+
+    fn read_with_format<M, R>(mut reader: R, format: Option<PhenopacketFormat>) -> Result<M>
+    where
+        M: Message + DeserializeOwned + Default,
+        R: Read,
+    {
+        let mut content = vec![];
+        reader.read_to_end(&mut content).map_err(|e| Error::io(format!("Could not read input: {e}")))?;
+        match format.unwrap_or_else(|| PhenopacketFormat::detect(&content)) {
+            PhenopacketFormat::Json => decode_json(&content),
+            PhenopacketFormat::Yaml => decode_yaml(&content),
+            PhenopacketFormat::Protobuf => decode_protobuf(&content),
+        }
+    }
+}
+
+/// Parse the JSON into a value and convert it with [`proto_json::from_value`]. Errors of the conversion have no
+/// location, so the content is parsed again directly into the message to find the location of the error.
+fn decode_json<M: DeserializeOwned>(content: &[u8]) -> Result<M> {
+    let json_error = |e: serde_json::Error| Error::ParseError {
+        msg: format!("Could not parse JSON: {e}"),
+        line: Some(e.line()),
+        column: Some(e.column()),
+    };
+    let value = serde_json::from_slice(content).map_err(json_error)?;
+    proto_json::from_value(value).map_err(|e| match serde_json::from_slice::<M>(content) {
+        Err(located) => json_error(located),
+        Ok(_) => Error::ParseError { msg: format!("Could not parse JSON: {e}"), line: None, column: None },
+    })
+}
+
+/// Same as [`decode_json`] for YAML.
+fn decode_yaml<M: DeserializeOwned>(content: &[u8]) -> Result<M> {
+    let yaml_error = |e: serde_yaml::Error| {
+        let location = e.location();
+        Error::ParseError {
+            msg: format!("Could not parse YAML: {e}"),
+            line: location.as_ref().map(|l| l.line()),
+            column: location.as_ref().map(|l| l.column()),
+        }
+    };
+    let value = serde_yaml::from_slice(content).map_err(yaml_error)?;
+    proto_json::from_value(value).map_err(|e| match serde_yaml::from_slice::<M>(content) {
+        Err(located) => yaml_error(located),
+        Ok(_) => Error::ParseError { msg: format!("Could not parse YAML: {e}"), line: None, column: None },
+    })
+}
+
+/// Decode a length-delimited message (as written by [`crate::io::protobuf_printer::ProtobufPrinter`]) or,
+/// if the content is not exactly one length-delimited message, a plain message.
+fn decode_protobuf<M: Message + Default>(content: &[u8]) -> Result<M> {
+    let mut bytes = content;
+    if let Ok(message) = M::decode_length_delimited(&mut bytes) {
+        if bytes.is_empty() {
+            return Ok(message);
+        }
+    }
+    M::decode(content).map_err(|e| Error::ParseError {
+        msg: format!("Could not decode protobuf: {e}"),
+        line: None,
+        column: None,
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::io::json_printer::JsonPrinter;
+    use crate::io::phenopacket_printer::PhenopacketPrinter;
+    use crate::io::protobuf_printer::ProtobufPrinter;
+    use crate::io::yaml_printer::YamlPrinter;
+    use phenopackets::schema::v2::{Cohort, Family, Phenopacket};
+    use prost_types::Timestamp;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn phenopacket() -> Phenopacket {
+        let created = Timestamp { seconds: 1620988500, nanos: 0 };
+        let mut pp = Builder::phenopacket("PMID:1", Builder::meta_data(created, "anonymous biocurator"));
+        pp.subject = Some(Builder::individual("proband"));
+        pp
+    }
+
+    fn print(printer: &impl PhenopacketPrinter, pp: &Phenopacket) -> Vec<u8> {
+        let mut buffer = vec![];
+        printer.print(pp, &mut buffer).unwrap();
+        buffer
+    }
+
+    #[rstest]
+    #[case("pp.json", Some(PhenopacketFormat::Json))]
+    #[case("dir/pp.YAML", Some(PhenopacketFormat::Yaml))]
+    #[case("pp.yml", Some(PhenopacketFormat::Yaml))]
+    #[case("pp.pb", Some(PhenopacketFormat::Protobuf))]
+    #[case("pp.txt", None)]
+    #[case("pp", None)]
+    fn test_format_from_path(#[case] path: &str, #[case] expected: Option<PhenopacketFormat>) {
+        assert_eq!(expected, PhenopacketFormat::from_path(path));
+    }
+
+    #[rstest]
+    fn test_detect_and_read(phenopacket: Phenopacket) {
+        let json = print(&JsonPrinter::pretty(), &phenopacket);
+        let yaml = print(&YamlPrinter, &phenopacket);
+        let protobuf = print(&ProtobufPrinter, &phenopacket);
+        assert_eq!(PhenopacketFormat::Json, PhenopacketFormat::detect(&json));
+        assert_eq!(PhenopacketFormat::Yaml, PhenopacketFormat::detect(&yaml));
+        assert_eq!(PhenopacketFormat::Protobuf, PhenopacketFormat::detect(&protobuf));
+        let reader = PhenopacketReader::new();
+        for content in [json, yaml, protobuf, phenopacket.encode_to_vec()] {
+            let pp: Phenopacket = reader.read(content.as_slice()).unwrap();
+            assert_eq!(phenopacket, pp);
+        }
+    }
+
+    #[rstest]
+    fn test_read_family_and_cohort(phenopacket: Phenopacket) {
+        let family = Family { id: "family".to_string(), proband: Some(phenopacket.clone()), ..Default::default() };
+        let bytes = family.encode_length_delimited_to_vec();
+        assert_eq!(family, PhenopacketReader::new().read::<Family, _>(bytes.as_slice()).unwrap());
+        let cohort = Cohort { id: "cohort".to_string(), members: vec![phenopacket], ..Default::default() };
+        let json = JsonPrinter::compact().to_string(&cohort).unwrap();
+        assert_eq!(cohort, PhenopacketReader::new().read::<Cohort, _>(json.as_bytes()).unwrap());
+    }
+
+    #[rstest]
+    fn test_parse_error_location() {
+        let json = "{\n  \"id\": \"PMID:1\",\n  \"subject\": 42\n}";
+        let error = PhenopacketReader::new().read::<Phenopacket, _>(json.as_bytes()).unwrap_err();
+        let Error::ParseError { line, column, .. } = error else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(Some(3), line);
+        assert!(column.is_some());
+    }
+
+    #[rstest]
+    fn test_read_path(phenopacket: Phenopacket) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pp.yml");
+        YamlPrinter.print_to_path(&phenopacket, &path).unwrap();
+        let pp: Phenopacket = PhenopacketReader::new().read_path(&path).unwrap();
+        assert_eq!(phenopacket, pp);
+        let missing = PhenopacketReader::new().read_path::<Phenopacket>(dir.path().join("missing.json"));
+        assert!(matches!(missing, Err(Error::IoError { .. })));
+    }
+}
@@ -0,0 +1,286 @@
+//! The canonical proto3 JSON mapping of phenopacket messages
+//!
+//! The serde implementations of the `phenopackets` crate use the proto3 field names, write enums as strings,
+//! timestamps as RFC3339 strings, and flatten oneofs such as `TimeElement`. This module adds the remaining parts
+//! of the [proto3 JSON mapping](https://protobuf.dev/programming-guides/json/):
+//!
+//! * emitting: fields with default values (including the zero value of enums) are omitted, and 64-bit integers
+//!   are written as strings
+//! * parsing: the original (snake_case) field names, enum values given as numbers, 64-bit integers given as
+//!   strings, and `null` for absent fields are accepted
+//!
+//! The functions work on [`serde_json::Value`]s, so they apply equally to JSON and YAML.
+use phenopackets::ga4gh::vrsatile::v1::MoleculeContext;
+use phenopackets::schema::v2::core::genomic_interpretation::InterpretationStatus;
+use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+use phenopackets::schema::v2::core::pedigree::person::AffectedStatus;
+use phenopackets::schema::v2::core::therapeutic_regimen::RegimenStatus;
+use phenopackets::schema::v2::core::vital_status::Status;
+use phenopackets::schema::v2::core::{AcmgPathogenicityClassification, DrugType, KaryotypicSex, Sex, TherapeuticActionability};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+type EnumName = fn(i32) -> Option<&'static str>;
+
+/// Enum fields with the function that maps a number to the name of the enum value.
+const ENUM_FIELDS: [(&str, EnumName); 11] = [
+    ("progressStatus", |v| ProgressStatus::try_from(v).ok().map(|e| e.as_str_name())),
+    ("interpretationStatus", |v| InterpretationStatus::try_from(v).ok().map(|e| e.as_str_name())),
+    ("acmgPathogenicityClassification", |v| AcmgPathogenicityClassification::try_from(v).ok().map(|e| e.as_str_name())),
+    ("therapeuticActionability", |v| TherapeuticActionability::try_from(v).ok().map(|e| e.as_str_name())),
+    ("status", |v| Status::try_from(v).ok().map(|e| e.as_str_name())),
+    ("sex", |v| Sex::try_from(v).ok().map(|e| e.as_str_name())),
+    ("karyotypicSex", |v| KaryotypicSex::try_from(v).ok().map(|e| e.as_str_name())),
+    ("regimenStatus", |v| RegimenStatus::try_from(v).ok().map(|e| e.as_str_name())),
+    ("drugType", |v| DrugType::try_from(v).ok().map(|e| e.as_str_name())),
+    ("affectedStatus", |v| AffectedStatus::try_from(v).ok().map(|e| e.as_str_name())),
+    ("moleculeContext", |v| MoleculeContext::try_from(v).ok().map(|e| e.as_str_name())),
+];
+
+/// Fields with protobuf map values. Their keys are data and their entries are kept even if the value is empty.
+const MAP_FIELDS: [&str; 3] = ["fileAttributes", "individualToFileIdentifiers", "individualToSampleIdentifiers"];
+
+/// The 64-bit integer fields by the name of the field that holds their message: `VcfRecord.pos` and the fields of
+/// the VRS `Number`, `IndefiniteRange`, `DefiniteRange`, and `SimpleInterval` (flattened from the oneofs of
+/// `SequenceInterval` and `SequenceLocation`). Fields such as `Extension.value` are strings and must not be converted.
+const INT64_FIELDS: [(&str, &[&str]); 8] = [
+    ("vcfRecord", &["pos"]),
+    ("startNumber", &["value"]),
+    ("endNumber", &["value"]),
+    ("startIndefiniteRange", &["value"]),
+    ("endIndefiniteRange", &["value"]),
+    ("startDefiniteRange", &["min", "max"]),
+    ("endDefiniteRange", &["min", "max"]),
+    ("simpleInterval", &["start", "end"]),
+];
+
+
+/// Serialize a message to its canonical proto3 JSON value.
+pub fn to_value<M: Serialize>(message: &M) -> serde_json::Result<Value> {
+    let mut value = serde_json::to_value(message)?;
+    canonicalize(&mut value);
+    Ok(value)
+}
+
+/// Deserialize a message from a proto3 JSON value, accepting all the variations allowed by the mapping.
+pub fn from_value<M: DeserializeOwned>(mut value: Value) -> serde_json::Result<M> {
+    normalize(&mut value);
+    serde_json::from_value(value)
+}
+
+
+fn canonicalize(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, v| !is_default(key, v));
+            for (key, v) in map.iter_mut() {
+                if !MAP_FIELDS.contains(&key.as_str()) {
+                    canonicalize(v);
+                }
+                for field in int64_fields(key, v) {
+                    if let Some(n) = field.as_number().filter(|n| n.is_u64() || n.is_i64()) {
+                        *field = Value::String(n.to_string());
+                    }
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(canonicalize),
+        _ => {}
+    }
+}
+
+fn is_default(key: &str, value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(b) => !b,
+        Value::Number(n) => n.as_f64() == Some(0.0),
+        Value::String(s) => s.is_empty() || enum_name(key, 0).is_some_and(|default| s == default),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(map) => MAP_FIELDS.contains(&key) && map.is_empty(),
+    }
+}
+
+fn enum_name(key: &str, number: i64) -> Option<&'static str> {
+    let (_, name) = ENUM_FIELDS.iter().find(|(field, _)| *field == key)?;
+    name(i32::try_from(number).ok()?)
+}
+
+fn normalize(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let entries = std::mem::take(map);
+            let keys: Vec<String> = entries.keys().map(|k| lower_camel_case(k)).collect();
+            for (key, mut v) in keys.into_iter().zip(entries.into_values()) {
+                if v.is_null() {
+                    continue;
+                }
+                if let Some(name) = v.as_i64().and_then(|n| enum_name(&key, n)) {
+                    v = Value::String(name.to_string());
+                }
+                if !MAP_FIELDS.contains(&key.as_str()) {
+                    normalize(&mut v);
+                }
+                for field in int64_fields(&key, &mut v) {
+                    if let Some(n) = field.as_str().and_then(parse_int64) {
+                        *field = n;
+                    }
+                }
+                map.insert(key, v);
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(normalize),
+        _ => {}
+    }
+}
+
+/// The 64-bit integer fields of the message `value` held by the field `key` (see [`INT64_FIELDS`]).
+fn int64_fields<'a>(key: &str, value: &'a mut Value) -> impl Iterator<Item = &'a mut Value> {
+    let names = INT64_FIELDS.iter().find(|(k, _)| *k == key).map_or(&[][..], |(_, names)| *names);
+    value
+        .as_object_mut()
+        .into_iter()
+        .flat_map(|map| map.iter_mut())
+        .filter(move |(name, _)| names.contains(&name.as_str()))
+        .map(|(_, v)| v)
+}
+
+fn parse_int64(s: &str) -> Option<Value> {
+    s.parse::<u64>().map(Value::from).or_else(|_| s.parse::<i64>().map(Value::from)).ok()
+}
+
+/// Convert a snake_case proto field name to its JSON name, e.g., `time_at_last_encounter` -> `timeAtLastEncounter`.
+fn lower_camel_case(name: &str) -> String {
+    if !name.contains('_') || name.starts_with('_') {
+        return name.to_string();
+    }
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            camel.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use phenopackets::ga4gh::vrs::v1::sequence_interval::{End, Start};
+    use phenopackets::ga4gh::vrs::v1::sequence_location::Interval;
+    use phenopackets::ga4gh::vrs::v1::{DefiniteRange, Number, SequenceInterval, SequenceLocation};
+    use phenopackets::ga4gh::vrsatile::v1::{Expression, Extension, VariationDescriptor};
+    use phenopackets::schema::v2::core::Individual;
+    use phenopackets::schema::v2::Phenopacket;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    fn test_to_value() {
+        let mut vd = Builder::variation_descriptor("var1");
+        vd.vcf_record = Some(Builder::vcf_record("GRCh38", "chr21", 45989626, "G", "A"));
+        let value = to_value(&vd).unwrap();
+        assert_eq!(
+            json!({"id": "var1", "vcfRecord": {"genomeAssembly": "GRCh38", "chrom": "chr21", "pos": "45989626", "ref": "G", "alt": "A"}}),
+            value);
+        assert_eq!(vd, from_value::<VariationDescriptor>(value).unwrap());
+    }
+
+    #[rstest]
+    fn test_enums_and_timestamps() {
+        let mut individual = Builder::individual("proband");
+        individual.sex = Sex::Female.into();
+        assert_eq!(json!({"id": "proband", "sex": "FEMALE"}), to_value(&individual).unwrap());
+        let pp = Builder::phenopacket("id", Builder::meta_data(prost_types::Timestamp { seconds: 0, nanos: 500_000_000 }, "me"));
+        let value = to_value(&pp).unwrap();
+        assert_eq!(json!("1970-01-01T00:00:00.500Z"), value["metaData"]["created"]);
+    }
+
+    #[rstest]
+    fn test_from_value_variations() {
+        let value = json!({
+            "id": "proband",
+            "alternate_ids": ["P1"],
+            "sex": 2,
+            "karyotypic_sex": null,
+            "time_at_last_encounter": {"age": {"iso8601duration": "P6Y"}},
+        });
+        let individual: Individual = from_value(value).unwrap();
+        assert_eq!(vec!["P1".to_string()], individual.alternate_ids);
+        assert_eq!(Sex::Male, individual.sex());
+        assert!(individual.time_at_last_encounter.is_some());
+    }
+
+    #[rstest]
+    fn test_string_value_fields_are_kept() {
+        let value = json!({
+            "id": "var1",
+            "expressions": [{"syntax": "hgvs.c", "value": "123"}],
+            "extensions": [{"name": "allele-frequency", "value": "42"}],
+        });
+        let vd: VariationDescriptor = from_value(value).unwrap();
+        assert_eq!("123", vd.expressions[0].value);
+        assert_eq!("42", vd.extensions[0].value);
+    }
+
+    #[rstest]
+    fn test_value_only_strings_round_trip() {
+        let mut vd = Builder::variation_descriptor("var1");
+        vd.expressions.push(Expression { value: "123".to_string(), ..Default::default() });
+        vd.extensions.push(Extension { name: String::new(), value: "42".to_string() });
+        let value = to_value(&vd).unwrap();
+        assert_eq!(json!([{"value": "123"}]), value["expressions"]);
+        assert_eq!(json!([{"value": "42"}]), value["extensions"]);
+        assert_eq!(vd, from_value::<VariationDescriptor>(value).unwrap());
+    }
+
+    #[rstest]
+    fn test_vrs_numbers_round_trip() {
+        let location = SequenceLocation {
+            id: "loc1".to_string(),
+            sequence_id: "refseq:NC_000021.9".to_string(),
+            interval: Some(Interval::SequenceInterval(SequenceInterval {
+                start: Some(Start::StartNumber(Number { value: 45989625 })),
+                end: Some(End::EndDefiniteRange(DefiniteRange { min: 45989626, max: 45989700 })),
+            })),
+        };
+        let value = to_value(&location).unwrap();
+        assert_eq!(
+            json!({"startNumber": {"value": "45989625"}, "endDefiniteRange": {"min": "45989626", "max": "45989700"}}),
+            value["sequenceInterval"]);
+        assert_eq!(location, from_value::<SequenceLocation>(value).unwrap());
+    }
+
+    #[rstest]
+    fn test_negative_int64_round_trip() {
+        let value = to_value(&json!({"vcfRecord": {"pos": -5}})).unwrap();
+        assert_eq!(json!({"vcfRecord": {"pos": "-5"}}), value);
+        assert_eq!(json!({"vcfRecord": {"pos": -5}}), from_value::<Value>(value).unwrap());
+    }
+
+    #[rstest]
+    fn test_map_fields_are_kept() {
+        let value = json!({"id": "pp", "files": [{"uri": "file:///a.vcf", "file_attributes": {"genome_assembly": ""}}]});
+        let pp: Phenopacket = from_value(value).unwrap();
+        assert_eq!(Some(&String::new()), pp.files[0].file_attributes.get("genome_assembly"));
+        let value = to_value(&pp).unwrap();
+        assert_eq!(json!({"genome_assembly": ""}), value["files"][0]["fileAttributes"]);
+    }
+
+    #[rstest]
+    #[case("time_at_last_encounter", "timeAtLastEncounter")]
+    #[case("iso8601duration", "iso8601duration")]
+    #[case("id", "id")]
+    #[case("_id", "_id")]
+    fn test_lower_camel_case(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(expected, lower_camel_case(name));
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+use prost::Message;
+use serde::Serialize;
+
+use crate::io::phenopacket_printer::PhenopacketPrinter;
+
+/// Print messages in the binary protobuf wire format, each prefixed with its length as a varint.
+///
+/// Several messages can be written to the same stream and read back with `Message::decode_length_delimited`.
+#[derive(Debug, Clone, Default)]
+pub struct ProtobufPrinter;
+
+impl PhenopacketPrinter for ProtobufPrinter {
+    fn print<M, W>(&self, message: &M, writer: &mut W) -> io::Result<()>
+    where
+        M: Message + Serialize,
+        W: Write,
+    {
+        writer.write_all(&message.encode_length_delimited_to_vec())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use phenopackets::schema::v2::Phenopacket;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_round_trip() {
+        let first = Builder::phenopacket("first", Builder::meta_data_now("anonymous biocurator"));
+        let second = Builder::phenopacket("second", Builder::meta_data_now("anonymous biocurator"));
+        let mut buffer = vec![];
+        ProtobufPrinter.print(&first, &mut buffer).unwrap();
+        ProtobufPrinter.print(&second, &mut buffer).unwrap();
+        let mut bytes = buffer.as_slice();
+        assert_eq!(first, Phenopacket::decode_length_delimited(&mut bytes).unwrap());
+        assert_eq!(second, Phenopacket::decode_length_delimited(&mut bytes).unwrap());
+        assert!(bytes.is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use std::io::{self, Write};
+
+use prost::Message;
+use serde::Serialize;
+
+use crate::io::phenopacket_printer::PhenopacketPrinter;
+use crate::io::proto_json;
+
+/// Print messages as YAML, following the same proto3 JSON mapping as [`crate::io::json_printer::JsonPrinter`].
+#[derive(Debug, Clone, Default)]
+pub struct YamlPrinter;
+
+impl YamlPrinter {
+    /// Print the message to a String.
+    pub fn to_string<M: Serialize>(&self, message: &M) -> io::Result<String> {
+        let value = proto_json::to_value(message)?;
+        serde_yaml::to_string(&value).map_err(io::Error::other)
+    }
+}
+
+impl PhenopacketPrinter for YamlPrinter {
+    fn print<M, W>(&self, message: &M, writer: &mut W) -> io::Result<()>
+    where
+        M: Message + Serialize,
+        W: Write,
+    {
+        writer.write_all(self.to_string(message)?.as_bytes())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_print() {
+        let mut individual = Builder::individual("proband");
+        individual.alternate_ids.push("P1".to_string());
+        let yaml = YamlPrinter.to_string(&individual).unwrap();
+        assert_eq!("id: proband\nalternateIds:\n- P1\n", yaml);
+    }
+}
@@ -4,5 +4,10 @@
 
 pub mod builders;
 pub mod constants;
+pub mod converters;
 pub mod error;
 pub mod io;
+pub mod ontology;
+pub mod ontology_class_visitor;
+pub mod tabular;
+pub mod validation;
@@ -0,0 +1,12 @@
+//! Local ontologies (e.g., HPO) for checking and completing OntologyClass messages
+//!
+//! An [`ontology_graph::Ontology`] is loaded from a local obographs JSON file (e.g., `hp.json`) or
+//! an OBO file (e.g., `hp.obo`). It contains the primary labels, synonyms, alternate ids,
+//! obsolete flags, and is_a edges of all terms and works entirely offline.
+pub mod obo_parser;
+pub mod obographs_parser;
+pub mod ontology_graph;
+pub mod term;
+
+#[cfg(test)]
+pub(crate) mod test_ontology;
@@ -0,0 +1,143 @@
+//! Parse ontologies in OBO 1.4 format (e.g., `hp.obo`)
+//!
+//! Only the `[Term]` stanzas and the header `data-version` are used; the tags id, name, synonym,
+//! alt_id, is_a, is_obsolete, replaced_by, and consider are extracted, all other tags are ignored.
+use std::io::BufRead;
+
+use crate::error::{Error, Result};
+use crate::ontology::ontology_graph::Ontology;
+use crate::ontology::term::Term;
+
+
+/// Read an ontology in OBO format.
+pub fn read_obo<R: BufRead>(reader: R) -> Result<Ontology> {
+    let mut version = None;
+    let mut terms = vec![];
+    let mut current: Option<Term> = None;
+    let mut in_header = true;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| Error::ontology(format!("Could not read OBO line {}: {e}", i + 1)))?;
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_header = false;
+            if let Some(term) = current.take() {
+                terms.push(term);
+            }
+            if line == "[Term]" {
+                current = Some(Term::default());
+            }
+            continue;
+        }
+        let Some((tag, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = strip_comment(value.trim());
+        if in_header {
+            if tag == "data-version" {
+                version = Some(value.to_string());
+            }
+            continue;
+        }
+        let Some(term) = current.as_mut() else {
+            continue;
+        };
+        match tag {
+            "id" => term.id = value.to_string(),
+            "name" => term.label = value.to_string(),
+            "synonym" => {
+                if let Some(synonym) = quoted(value) {
+                    term.synonyms.push(synonym.to_string());
+                }
+            }
+            "alt_id" => term.alt_ids.push(value.to_string()),
+            "is_a" => term.parents.push(first_token(value).to_string()),
+            "is_obsolete" => term.obsolete = value == "true",
+            "replaced_by" => term.replaced_by = Some(value.to_string()),
+            "consider" => term.consider.push(value.to_string()),
+            _ => {}
+        }
+    }
+    if let Some(term) = current.take() {
+        terms.push(term);
+    }
+    if let Some(term) = terms.iter().find(|t| t.id.is_empty()) {
+        return Err(Error::ontology(format!("OBO [Term] stanza without id (name: '{}')", term.label)));
+    }
+    Ok(Ontology::new(terms, version))
+}
+
+/// Remove a trailing `! comment` (as in `is_a: HP:0000118 ! Phenotypic abnormality`).
+fn strip_comment(value: &str) -> &str {
+    match value.find(" !") {
+        Some(pos) => value[..pos].trim_end(),
+        None => value,
+    }
+}
+
+fn first_token(value: &str) -> &str {
+    value.split_whitespace().next().unwrap_or(value)
+}
+
+/// Extract the quoted text of a synonym line, e.g., `"VSD" EXACT []`.
+fn quoted(value: &str) -> Option<&str> {
+    let start = value.find('"')? + 1;
+    let end = start + value[start..].find('"')?;
+    Some(&value[start..end])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const OBO: &str = r#"format-version: 1.2
+data-version: hp/releases/2025-03-03
+ontology: hp
+
+[Term]
+id: HP:0000118
+name: Phenotypic abnormality
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0001629
+name: Ventricular septal defect
+alt_id: HP:0001630
+synonym: "VSD" EXACT [] ! abbreviation
+synonym: "Interventricular septal defect" EXACT []
+is_a: HP:0010438 ! Abnormal ventricular septum morphology
+xref: UMLS:C0018818
+
+[Term]
+id: HP:0006000
+name: obsolete Ventricular hole
+is_obsolete: true
+replaced_by: HP:0001629
+
+[Typedef]
+id: part_of
+name: part of
+"#;
+
+    #[rstest]
+    fn test_read_obo() {
+        let ontology = read_obo(OBO.as_bytes()).unwrap();
+        assert_eq!(Some("hp/releases/2025-03-03"), ontology.version());
+        assert_eq!(3, ontology.len());
+        let vsd = ontology.term("HP:0001629").unwrap();
+        assert_eq!("Ventricular septal defect", vsd.label);
+        assert_eq!(vec!["VSD", "Interventricular septal defect"], vsd.synonyms);
+        assert_eq!(vec!["HP:0001630"], vsd.alt_ids);
+        assert_eq!(vec!["HP:0010438"], vsd.parents);
+        let obsolete = ontology.term("HP:0006000").unwrap();
+        assert!(obsolete.obsolete);
+        assert_eq!(Some("HP:0001629".to_string()), obsolete.replaced_by);
+    }
+
+    #[rstest]
+    fn test_stanza_without_id() {
+        let result = read_obo("[Term]\nname: no id\n".as_bytes());
+        assert!(matches!(result, Err(Error::OntologyError { .. })));
+    }
+}
@@ -0,0 +1,197 @@
+//! Parse ontologies in obographs JSON format (e.g., `hp.json`)
+//!
+//! Only the first graph of the file is used. Class nodes provide the labels, synonyms, alternate ids,
+//! and obsolete (deprecated) flags; `is_a` edges provide the hierarchy.
+use std::collections::HashMap;
+use std::io::Read;
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::ontology::ontology_graph::Ontology;
+use crate::ontology::term::Term;
+
+const OBO_PURL: &str = "http://purl.obolibrary.org/obo/";
+const HAS_ALTERNATIVE_ID: &str = "http://www.geneontology.org/formats/oboInOwl#hasAlternativeId";
+const CONSIDER: &str = "http://www.geneontology.org/formats/oboInOwl#consider";
+const TERM_REPLACED_BY: &str = "http://purl.obolibrary.org/obo/IAO_0100001";
+
+
+#[derive(Deserialize)]
+struct GraphDocument {
+    #[serde(default)]
+    graphs: Vec<Graph>,
+}
+
+#[derive(Deserialize)]
+struct Graph {
+    #[serde(default)]
+    meta: Option<Meta>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    edges: Vec<Edge>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    id: String,
+    #[serde(default)]
+    lbl: Option<String>,
+    #[serde(default, rename = "type")]
+    node_type: Option<String>,
+    #[serde(default)]
+    meta: Option<Meta>,
+}
+
+#[derive(Deserialize, Default)]
+struct Meta {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    synonyms: Vec<PropertyValue>,
+    #[serde(default, rename = "basicPropertyValues")]
+    basic_property_values: Vec<PropertyValue>,
+    #[serde(default)]
+    deprecated: bool,
+}
+
+#[derive(Deserialize)]
+struct PropertyValue {
+    pred: String,
+    val: String,
+}
+
+#[derive(Deserialize)]
+struct Edge {
+    sub: String,
+    pred: String,
+    obj: String,
+}
+
+
+/// Convert an OBO PURL such as `http://purl.obolibrary.org/obo/HP_0001629` into a CURIE (`HP:0001629`).
+/// Values that are not OBO PURLs are returned unchanged.
+pub fn iri_to_curie(iri: &str) -> String {
+    match iri.strip_prefix(OBO_PURL) {
+        Some(local) => local.replacen('_', ":", 1),
+        None => iri.to_string(),
+    }
+}
+
+/// Convert a version IRI such as `http://purl.obolibrary.org/obo/hp/releases/2025-03-03/hp.json`
+/// into the OBO data-version (`hp/releases/2025-03-03`).
+fn version_from_iri(iri: &str) -> String {
+    let version = iri.strip_prefix(OBO_PURL).unwrap_or(iri);
+    match version.rsplit_once('/') {
+        Some((head, file)) if file.contains('.') => head.to_string(),
+        _ => version.to_string(),
+    }
+}
+
+
+/// Read an ontology in obographs JSON format.
+pub fn read_obographs<R: Read>(reader: R) -> Result<Ontology> {
+    let document: GraphDocument = serde_json::from_reader(reader)
+        .map_err(|e| Error::ontology(format!("Could not parse obographs JSON: {e}")))?;
+    let graph = document
+        .graphs
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::ontology("obographs JSON does not contain a graph"))?;
+    let version = graph
+        .meta
+        .as_ref()
+        .and_then(|m| m.version.as_deref())
+        .map(version_from_iri);
+    let mut terms: HashMap<String, Term> = HashMap::new();
+    for node in graph.nodes {
+        if node.node_type.as_deref() != Some("CLASS") {
+            continue;
+        }
+        let id = iri_to_curie(&node.id);
+        let meta = node.meta.unwrap_or_default();
+        let mut term = Term::new(id.clone(), node.lbl.unwrap_or_default());
+        term.obsolete = meta.deprecated;
+        term.synonyms = meta.synonyms.into_iter().map(|s| s.val).collect();
+        for pv in meta.basic_property_values {
+            match pv.pred.as_str() {
+                HAS_ALTERNATIVE_ID => term.alt_ids.push(pv.val),
+                TERM_REPLACED_BY => term.replaced_by = Some(iri_to_curie(&pv.val)),
+                CONSIDER => term.consider.push(iri_to_curie(&pv.val)),
+                _ => {}
+            }
+        }
+        terms.insert(id, term);
+    }
+    for edge in graph.edges {
+        if edge.pred != "is_a" {
+            continue;
+        }
+        if let Some(term) = terms.get_mut(&iri_to_curie(&edge.sub)) {
+            term.parents.push(iri_to_curie(&edge.obj));
+        }
+    }
+    Ok(Ontology::new(terms.into_values().collect(), version))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const OBOGRAPHS: &str = r#"{
+  "graphs": [{
+    "id": "http://purl.obolibrary.org/obo/hp.json",
+    "meta": {"version": "http://purl.obolibrary.org/obo/hp/releases/2025-03-03/hp.json"},
+    "nodes": [
+      {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality", "type": "CLASS"},
+      {"id": "http://purl.obolibrary.org/obo/HP_0001629", "lbl": "Ventricular septal defect", "type": "CLASS",
+       "meta": {
+         "synonyms": [{"pred": "hasExactSynonym", "val": "VSD"}],
+         "basicPropertyValues": [{"pred": "http://www.geneontology.org/formats/oboInOwl#hasAlternativeId", "val": "HP:0001630"}]
+       }},
+      {"id": "http://purl.obolibrary.org/obo/HP_0006000", "lbl": "obsolete Ventricular hole", "type": "CLASS",
+       "meta": {
+         "deprecated": true,
+         "basicPropertyValues": [{"pred": "http://purl.obolibrary.org/obo/IAO_0100001", "val": "http://purl.obolibrary.org/obo/HP_0001629"}]
+       }},
+      {"id": "http://purl.obolibrary.org/obo/hp#has_modifier", "lbl": "has modifier", "type": "PROPERTY"}
+    ],
+    "edges": [
+      {"sub": "http://purl.obolibrary.org/obo/HP_0001629", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000118"},
+      {"sub": "http://purl.obolibrary.org/obo/HP_0001629", "pred": "http://purl.obolibrary.org/obo/BFO_0000050", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+    ]
+  }]
+}"#;
+
+    #[rstest]
+    fn test_read_obographs() {
+        let ontology = read_obographs(OBOGRAPHS.as_bytes()).unwrap();
+        assert_eq!(Some("hp/releases/2025-03-03"), ontology.version());
+        assert_eq!(3, ontology.len());
+        let vsd = ontology.term("HP:0001629").unwrap();
+        assert_eq!("Ventricular septal defect", vsd.label);
+        assert_eq!(vec!["VSD"], vsd.synonyms);
+        assert_eq!(vec!["HP:0001630"], vsd.alt_ids);
+        assert_eq!(vec!["HP:0000118"], vsd.parents);
+        let obsolete = ontology.term("HP:0006000").unwrap();
+        assert!(obsolete.obsolete);
+        assert_eq!(Some("HP:0001629".to_string()), obsolete.replaced_by);
+    }
+
+    #[rstest]
+    #[case("http://purl.obolibrary.org/obo/HP_0001629", "HP:0001629")]
+    #[case("http://purl.obolibrary.org/obo/NCBITaxon_9606", "NCBITaxon:9606")]
+    #[case("HP:0001630", "HP:0001630")]
+    fn test_iri_to_curie(#[case] iri: &str, #[case] curie: &str) {
+        assert_eq!(curie, iri_to_curie(iri));
+    }
+
+    #[rstest]
+    fn test_invalid_json() {
+        let result = read_obographs("{\"graphs\": [".as_bytes());
+        assert!(matches!(result, Err(Error::OntologyError { .. })));
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::ontology::term::Term;
+use crate::ontology::{obo_parser, obographs_parser};
+
+
+impl Error {
+    pub(crate) fn ontology(msg: impl Into<String>) -> Self {
+        Error::OntologyError { msg: msg.into() }
+    }
+}
+
+
+/// An ontology with its terms and is_a hierarchy held in memory.
+#[derive(Debug, Clone, Default)]
+pub struct Ontology {
+    version: Option<String>,
+    terms: HashMap<String, Term>,
+    /// alternate id -> primary id
+    alt_ids: HashMap<String, String>,
+    /// term id -> ids of direct is_a children
+    children: HashMap<String, Vec<String>>,
+    /// lower-case label -> ids of the (non-obsolete) terms with this label
+    labels: HashMap<String, Vec<String>>,
+    /// lower-case synonym -> ids of the (non-obsolete) terms with this synonym
+    synonyms: HashMap<String, Vec<String>>,
+}
+
+impl Ontology {
+    /// Create an ontology from its terms. The is_a edges are taken from [`Term::parents`].
+    pub fn new(terms: Vec<Term>, version: Option<String>) -> Self {
+        let mut ontology = Ontology { version, ..Default::default() };
+        for term in terms {
+            for alt_id in &term.alt_ids {
+                ontology.alt_ids.insert(alt_id.clone(), term.id.clone());
+            }
+            for parent in &term.parents {
+                ontology.children.entry(parent.clone()).or_default().push(term.id.clone());
+            }
+            if !term.obsolete {
+                ontology.labels.entry(term.label.to_lowercase()).or_default().push(term.id.clone());
+                for synonym in &term.synonyms {
+                    ontology.synonyms.entry(synonym.to_lowercase()).or_default().push(term.id.clone());
+                }
+            }
+            ontology.terms.insert(term.id.clone(), term);
+        }
+        ontology
+    }
+
+    /// Load an ontology from an obographs JSON (`.json`) or OBO (`.obo`) file.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| Error::ontology(format!("Could not open ontology file {}: {e}", path.display())))?;
+        let reader = BufReader::new(file);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => obographs_parser::read_obographs(reader),
+            Some("obo") => obo_parser::read_obo(reader),
+            _ => Err(Error::ontology(format!(
+                "Unrecognized ontology file format (expected .json or .obo): {}", path.display()))),
+        }
+    }
+
+    /// The version (data-version) of the ontology, if known.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Number of terms (including obsolete terms).
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn terms(&self) -> impl Iterator<Item = &Term> {
+        self.terms.values()
+    }
+
+    /// Get a term by its primary id.
+    pub fn term(&self, id: &str) -> Option<&Term> {
+        self.terms.get(id)
+    }
+
+    /// Get a term by its primary or alternate id.
+    pub fn term_by_any_id(&self, id: &str) -> Option<&Term> {
+        self.term(id).or_else(|| self.alt_ids.get(id).and_then(|primary| self.term(primary)))
+    }
+
+    /// Return true if the id is an alternate id of some term.
+    pub fn is_alternate_id(&self, id: &str) -> bool {
+        self.alt_ids.contains_key(id)
+    }
+
+    /// Get the primary id for a primary or alternate id.
+    pub fn primary_id<'a>(&'a self, id: &'a str) -> Option<&'a str> {
+        if self.terms.contains_key(id) {
+            Some(id)
+        } else {
+            self.alt_ids.get(id).map(String::as_str)
+        }
+    }
+
+    /// Get the current (non-obsolete) term for an id, following alternate ids and `replaced_by` links.
+    /// Returns None if the id is unknown or refers to an obsolete term without replacement.
+    pub fn current_term(&self, id: &str) -> Option<&Term> {
+        let mut term = self.term_by_any_id(id)?;
+        let mut visited = HashSet::new();
+        while term.obsolete {
+            if !visited.insert(term.id.as_str()) {
+                return None;
+            }
+            term = self.term_by_any_id(term.replaced_by.as_deref()?)?;
+        }
+        Some(term)
+    }
+
+    /// Non-obsolete terms whose primary label matches (case-insensitive).
+    pub fn terms_by_label(&self, label: &str) -> Vec<&Term> {
+        Self::lookup(&self.labels, label).filter_map(|id| self.term(id)).collect()
+    }
+
+    /// Non-obsolete terms with a matching synonym (case-insensitive).
+    pub fn terms_by_synonym(&self, synonym: &str) -> Vec<&Term> {
+        Self::lookup(&self.synonyms, synonym).filter_map(|id| self.term(id)).collect()
+    }
+
+    fn lookup<'a>(index: &'a HashMap<String, Vec<String>>, key: &str) -> impl Iterator<Item = &'a String> {
+        index.get(&key.to_lowercase()).into_iter().flatten()
+    }
+
+    /// Ids of the direct is_a parents of a term.
+    pub fn parents(&self, id: &str) -> &[String] {
+        self.term(id).map(|t| t.parents.as_slice()).unwrap_or_default()
+    }
+
+    /// Ids of the direct is_a children of a term.
+    pub fn children(&self, id: &str) -> &[String] {
+        self.children.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Ids of all ancestors of a term (excluding the term itself).
+    pub fn ancestors(&self, id: &str) -> HashSet<&str> {
+        self.traverse(id, |term_id| self.parents(term_id))
+    }
+
+    /// Ids of all descendants of a term (excluding the term itself).
+    pub fn descendants(&self, id: &str) -> HashSet<&str> {
+        self.traverse(id, |term_id| self.children(term_id))
+    }
+
+    /// Return true if `ancestor_id` is a (strict) ancestor of `id`.
+    pub fn is_ancestor_of(&self, ancestor_id: &str, id: &str) -> bool {
+        self.ancestors(id).contains(ancestor_id)
+    }
+
+    /// Return true if `id` is equal to `ancestor_id` or is one of its descendants.
+    pub fn is_equal_or_descendant_of(&self, id: &str, ancestor_id: &str) -> bool {
+        id == ancestor_id || self.is_ancestor_of(ancestor_id, id)
+    }
+
+    fn traverse<'a, F>(&'a self, id: &str, next: F) -> HashSet<&'a str>
+    where
+        F: Fn(&str) -> &'a [String],
+    {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = next(id).iter().map(String::as_str).collect();
+        while let Some(term_id) = queue.pop_front() {
+            if visited.insert(term_id) {
+                queue.extend(next(term_id).iter().map(String::as_str));
+            }
+        }
+        visited
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::test_ontology::toy_hpo;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_term_lookup() {
+        let hpo = toy_hpo();
+        let vsd = hpo.term("HP:0001629").unwrap();
+        assert_eq!("Ventricular septal defect", vsd.label);
+        assert_eq!(Some("HP:0001629"), hpo.primary_id("HP:0001630"));
+        assert!(hpo.is_alternate_id("HP:0001630"));
+        assert_eq!("HP:0001629", hpo.term_by_any_id("HP:0001630").unwrap().id);
+        assert!(hpo.term("HP:9999999").is_none());
+    }
+
+    #[rstest]
+    #[case("HP:0001629", Some("HP:0001629"))]
+    #[case("HP:0001630", Some("HP:0001629"))]
+    #[case("HP:0006000", Some("HP:0001629"))]
+    #[case("HP:9999999", None)]
+    fn test_current_term(#[case] id: &str, #[case] current_id: Option<&str>) {
+        let hpo = toy_hpo();
+        assert_eq!(current_id, hpo.current_term(id).map(|t| t.id.as_str()));
+    }
+
+    #[rstest]
+    fn test_label_and_synonym_lookup() {
+        let hpo = toy_hpo();
+        let by_label: Vec<&str> = hpo.terms_by_label("cataract").iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(vec!["HP:0000518"], by_label);
+        // the obsolete term HP:0006000 also has the synonym VSD
+        let by_synonym: Vec<&str> = hpo.terms_by_synonym("VSD").iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(vec!["HP:0001629"], by_synonym);
+    }
+
+    #[rstest]
+    #[case("HP:0000118", "HP:0001629", true)]
+    #[case("HP:0000001", "HP:0001629", true)]
+    #[case("HP:0001629", "HP:0001629", false)]
+    #[case("HP:0001629", "HP:0000118", false)]
+    #[case("HP:0000478", "HP:0001629", false)]
+    fn test_is_ancestor_of(#[case] ancestor: &str, #[case] id: &str, #[case] expected: bool) {
+        assert_eq!(expected, toy_hpo().is_ancestor_of(ancestor, id));
+    }
+
+    #[rstest]
+    fn test_descendants() {
+        let hpo = toy_hpo();
+        let descendants = hpo.descendants("HP:0003674");
+        assert_eq!(HashSet::from(["HP:0003577", "HP:0003593"]), descendants);
+        assert_eq!(&["HP:0001627".to_string()], hpo.children("HP:0030680"));
+    }
+
+    #[rstest]
+    fn test_from_path_with_unknown_extension() {
+        let result = Ontology::from_path("hp.owl");
+        assert!(matches!(result, Err(Error::OntologyError { .. })));
+    }
+}
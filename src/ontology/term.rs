@@ -0,0 +1,28 @@
+/// A term (class) of an ontology.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Term {
+    /// Primary id as a CURIE, e.g., HP:0001629
+    pub id: String,
+    /// Primary label, e.g., Ventricular septal defect
+    pub label: String,
+    pub synonyms: Vec<String>,
+    /// Alternate ids that were merged into this term
+    pub alt_ids: Vec<String>,
+    pub obsolete: bool,
+    /// For obsolete terms, the id of the term that replaces it (if any)
+    pub replaced_by: Option<String>,
+    /// For obsolete terms, ids of terms that should be considered instead
+    pub consider: Vec<String>,
+    /// Ids of the direct is_a parents
+    pub parents: Vec<String>,
+}
+
+impl Term {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Term {
+            id: id.into(),
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! A small, simplified excerpt of the HPO for unit tests.
+//!
+//! HP:0001630 (alternate id), HP:0006000 and HP:0006001 (obsolete terms) are made up for testing.
+use crate::ontology::obo_parser::read_obo;
+use crate::ontology::ontology_graph::Ontology;
+
+const TOY_HPO: &str = r#"format-version: 1.2
+data-version: hp/releases/2025-03-03
+
+[Term]
+id: HP:0000001
+name: All
+
+[Term]
+id: HP:0000118
+name: Phenotypic abnormality
+synonym: "Organ abnormality" EXACT []
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0001626
+name: Abnormality of the cardiovascular system
+synonym: "Cardiovascular abnormality" EXACT []
+is_a: HP:0000118 ! Phenotypic abnormality
+
+[Term]
+id: HP:0030680
+name: Abnormality of cardiovascular system morphology
+synonym: "Cardiovascular anomaly" RELATED []
+is_a: HP:0001626 ! Abnormality of the cardiovascular system
+
+[Term]
+id: HP:0001627
+name: Abnormal heart morphology
+synonym: "Abnormality of cardiac morphology" EXACT []
+synonym: "Cardiovascular anomaly" RELATED []
+is_a: HP:0030680 ! Abnormality of cardiovascular system morphology
+
+[Term]
+id: HP:0010438
+name: Abnormal ventricular septum morphology
+is_a: HP:0001627 ! Abnormal heart morphology
+
+[Term]
+id: HP:0001629
+name: Ventricular septal defect
+alt_id: HP:0001630
+synonym: "VSD" EXACT []
+synonym: "Interventricular septal defect" EXACT []
+is_a: HP:0010438 ! Abnormal ventricular septum morphology
+
+[Term]
+id: HP:0000478
+name: Abnormality of the eye
+synonym: "Eye defect" EXACT []
+is_a: HP:0000118 ! Phenotypic abnormality
+
+[Term]
+id: HP:0000518
+name: Cataract
+synonym: "Clouding of the lens" EXACT []
+is_a: HP:0000478 ! Abnormality of the eye
+
+[Term]
+id: HP:0006000
+name: obsolete Ventricular hole
+synonym: "VSD" EXACT []
+is_obsolete: true
+replaced_by: HP:0001629
+
+[Term]
+id: HP:0006001
+name: obsolete Abnormal septum
+is_obsolete: true
+consider: HP:0010438
+
+[Term]
+id: HP:0000005
+name: Mode of inheritance
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0000006
+name: Autosomal dominant inheritance
+is_a: HP:0000005 ! Mode of inheritance
+
+[Term]
+id: HP:0012823
+name: Clinical modifier
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0012832
+name: Bilateral
+is_a: HP:0012823 ! Clinical modifier
+
+[Term]
+id: HP:0031797
+name: Clinical course
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0003674
+name: Onset
+is_a: HP:0031797 ! Clinical course
+
+[Term]
+id: HP:0003577
+name: Congenital onset
+is_a: HP:0003674 ! Onset
+
+[Term]
+id: HP:0003593
+name: Infantile onset
+is_a: HP:0003674 ! Onset
+
+[Term]
+id: HP:0040279
+name: Frequency
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0040283
+name: Occasional
+is_a: HP:0040279 ! Frequency
+"#;
+
+pub(crate) fn toy_hpo() -> Ontology {
+    read_obo(TOY_HPO.as_bytes()).expect("toy HPO must be valid OBO")
+}
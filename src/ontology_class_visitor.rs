@@ -0,0 +1,309 @@
+//! Walk every OntologyClass in a phenopacket
+//!
+//! The visitor calls a closure with the JSON pointer of each OntologyClass (e.g., `/phenotypicFeatures/0/onset/ontologyClass`)
+//! and a reference to the class itself. [`visit::phenopacket`] yields shared references and
+//! [`visit_mut::phenopacket`] yields mutable references, so that terms can be rewritten in place.
+use phenopackets::ga4gh::vrsatile::v1::VariationDescriptor;
+use phenopackets::schema::v2::core::genomic_interpretation::Call;
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::medical_action::Action;
+use phenopackets::schema::v2::core::therapeutic_regimen::Identifier;
+use phenopackets::schema::v2::core::{time_element, value, Biosample, Disease, Individual, Interpretation, Measurement, MedicalAction, OntologyClass, PhenotypicFeature, Procedure, Quantity, TimeElement};
+use phenopackets::schema::v2::Phenopacket;
+
+
+macro_rules! define_ontology_class_visitor {
+    ($module:ident, $iter:ident, $as_ref:ident $(, $m:tt)?) => {
+        pub mod $module {
+            use super::*;
+
+            /// Call `f` with the JSON pointer and the OntologyClass for every OntologyClass in the phenopacket.
+            pub fn phenopacket<'a, F>(pp: &'a $($m)? Phenopacket, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                if let Some(subject) = pp.subject.$as_ref() {
+                    individual(subject, "/subject", f);
+                }
+                for (i, feature) in pp.phenotypic_features.$iter().enumerate() {
+                    phenotypic_feature(feature, &format!("/phenotypicFeatures/{i}"), f);
+                }
+                for (i, m) in pp.measurements.$iter().enumerate() {
+                    measurement(m, &format!("/measurements/{i}"), f);
+                }
+                for (i, b) in pp.biosamples.$iter().enumerate() {
+                    biosample(b, &format!("/biosamples/{i}"), f);
+                }
+                for (i, interp) in pp.interpretations.$iter().enumerate() {
+                    interpretation(interp, &format!("/interpretations/{i}"), f);
+                }
+                for (i, d) in pp.diseases.$iter().enumerate() {
+                    disease(d, &format!("/diseases/{i}"), f);
+                }
+                for (i, action) in pp.medical_actions.$iter().enumerate() {
+                    medical_action(action, &format!("/medicalActions/{i}"), f);
+                }
+            }
+
+            fn optional<'a, F>(clz: Option<&'a $($m)? OntologyClass>, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                if let Some(clz) = clz {
+                    f(path, clz);
+                }
+            }
+
+            fn repeated<'a, F>(classes: &'a $($m)? [OntologyClass], path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                for (i, clz) in classes.$iter().enumerate() {
+                    f(&format!("{path}/{i}"), clz);
+                }
+            }
+
+            fn time_element<'a, F>(te: Option<&'a $($m)? TimeElement>, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                if let Some(time_element::Element::OntologyClass(clz)) = te.and_then(|t| t.element.$as_ref()) {
+                    f(&format!("{path}/ontologyClass"), clz);
+                }
+            }
+
+            fn individual<'a, F>(ind: &'a $($m)? Individual, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                time_element(ind.time_at_last_encounter.$as_ref(), &format!("{path}/timeAtLastEncounter"), f);
+                if let Some(vs) = ind.vital_status.$as_ref() {
+                    time_element(vs.time_of_death.$as_ref(), &format!("{path}/vitalStatus/timeOfDeath"), f);
+                    optional(vs.cause_of_death.$as_ref(), &format!("{path}/vitalStatus/causeOfDeath"), f);
+                }
+                optional(ind.gender.$as_ref(), &format!("{path}/gender"), f);
+                optional(ind.taxonomy.$as_ref(), &format!("{path}/taxonomy"), f);
+            }
+
+            fn phenotypic_feature<'a, F>(pf: &'a $($m)? PhenotypicFeature, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(pf.r#type.$as_ref(), &format!("{path}/type"), f);
+                optional(pf.severity.$as_ref(), &format!("{path}/severity"), f);
+                repeated(& $($m)? pf.modifiers, &format!("{path}/modifiers"), f);
+                time_element(pf.onset.$as_ref(), &format!("{path}/onset"), f);
+                time_element(pf.resolution.$as_ref(), &format!("{path}/resolution"), f);
+                for (i, ev) in pf.evidence.$iter().enumerate() {
+                    optional(ev.evidence_code.$as_ref(), &format!("{path}/evidence/{i}/evidenceCode"), f);
+                }
+            }
+
+            fn quantity<'a, F>(q: &'a $($m)? Quantity, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(q.unit.$as_ref(), &format!("{path}/unit"), f);
+                if let Some(rr) = q.reference_range.$as_ref() {
+                    optional(rr.unit.$as_ref(), &format!("{path}/referenceRange/unit"), f);
+                }
+            }
+
+            fn procedure<'a, F>(p: &'a $($m)? Procedure, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(p.code.$as_ref(), &format!("{path}/code"), f);
+                optional(p.body_site.$as_ref(), &format!("{path}/bodySite"), f);
+                time_element(p.performed.$as_ref(), &format!("{path}/performed"), f);
+            }
+
+            fn measurement<'a, F>(m: &'a $($m)? Measurement, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(m.assay.$as_ref(), &format!("{path}/assay"), f);
+                match m.measurement_value.$as_ref() {
+                    Some(MeasurementValue::Value(v)) => match v.value.$as_ref() {
+                        Some(value::Value::Quantity(q)) => quantity(q, &format!("{path}/value/quantity"), f),
+                        Some(value::Value::OntologyClass(clz)) => f(&format!("{path}/value/ontologyClass"), clz),
+                        None => {}
+                    },
+                    Some(MeasurementValue::ComplexValue(cv)) => {
+                        for (i, tq) in cv.typed_quantities.$iter().enumerate() {
+                            let tq_path = format!("{path}/complexValue/typedQuantities/{i}");
+                            optional(tq.r#type.$as_ref(), &format!("{tq_path}/type"), f);
+                            if let Some(q) = tq.quantity.$as_ref() {
+                                quantity(q, &format!("{tq_path}/quantity"), f);
+                            }
+                        }
+                    }
+                    None => {}
+                }
+                time_element(m.time_observed.$as_ref(), &format!("{path}/timeObserved"), f);
+                if let Some(p) = m.procedure.$as_ref() {
+                    procedure(p, &format!("{path}/procedure"), f);
+                }
+            }
+
+            fn biosample<'a, F>(b: &'a $($m)? Biosample, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(b.sampled_tissue.$as_ref(), &format!("{path}/sampledTissue"), f);
+                optional(b.sample_type.$as_ref(), &format!("{path}/sampleType"), f);
+                for (i, feature) in b.phenotypic_features.$iter().enumerate() {
+                    phenotypic_feature(feature, &format!("{path}/phenotypicFeatures/{i}"), f);
+                }
+                for (i, m) in b.measurements.$iter().enumerate() {
+                    measurement(m, &format!("{path}/measurements/{i}"), f);
+                }
+                optional(b.taxonomy.$as_ref(), &format!("{path}/taxonomy"), f);
+                time_element(b.time_of_collection.$as_ref(), &format!("{path}/timeOfCollection"), f);
+                optional(b.histological_diagnosis.$as_ref(), &format!("{path}/histologicalDiagnosis"), f);
+                optional(b.tumor_progression.$as_ref(), &format!("{path}/tumorProgression"), f);
+                optional(b.tumor_grade.$as_ref(), &format!("{path}/tumorGrade"), f);
+                optional(b.pathological_stage.$as_ref(), &format!("{path}/pathologicalStage"), f);
+                repeated(& $($m)? b.pathological_tnm_finding, &format!("{path}/pathologicalTnmFinding"), f);
+                repeated(& $($m)? b.diagnostic_markers, &format!("{path}/diagnosticMarkers"), f);
+                if let Some(p) = b.procedure.$as_ref() {
+                    procedure(p, &format!("{path}/procedure"), f);
+                }
+                optional(b.material_sample.$as_ref(), &format!("{path}/materialSample"), f);
+                optional(b.sample_processing.$as_ref(), &format!("{path}/sampleProcessing"), f);
+                optional(b.sample_storage.$as_ref(), &format!("{path}/sampleStorage"), f);
+            }
+
+            fn variation_descriptor<'a, F>(vd: &'a $($m)? VariationDescriptor, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(vd.structural_type.$as_ref(), &format!("{path}/structuralType"), f);
+                optional(vd.allelic_state.$as_ref(), &format!("{path}/allelicState"), f);
+            }
+
+            fn interpretation<'a, F>(interp: &'a $($m)? Interpretation, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                if let Some(dx) = interp.diagnosis.$as_ref() {
+                    optional(dx.disease.$as_ref(), &format!("{path}/diagnosis/disease"), f);
+                    for (i, gi) in dx.genomic_interpretations.$iter().enumerate() {
+                        if let Some(Call::VariantInterpretation(vi)) = gi.call.$as_ref() {
+                            if let Some(vd) = vi.variation_descriptor.$as_ref() {
+                                let vd_path = format!("{path}/diagnosis/genomicInterpretations/{i}/variantInterpretation/variationDescriptor");
+                                variation_descriptor(vd, &vd_path, f);
+                            }
+                        }
+                    }
+                }
+            }
+
+            fn disease<'a, F>(d: &'a $($m)? Disease, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                optional(d.term.$as_ref(), &format!("{path}/term"), f);
+                time_element(d.onset.$as_ref(), &format!("{path}/onset"), f);
+                time_element(d.resolution.$as_ref(), &format!("{path}/resolution"), f);
+                repeated(& $($m)? d.disease_stage, &format!("{path}/diseaseStage"), f);
+                repeated(& $($m)? d.clinical_tnm_finding, &format!("{path}/clinicalTnmFinding"), f);
+                optional(d.primary_site.$as_ref(), &format!("{path}/primarySite"), f);
+                optional(d.laterality.$as_ref(), &format!("{path}/laterality"), f);
+            }
+
+            fn medical_action<'a, F>(ma: &'a $($m)? MedicalAction, path: &str, f: &mut F)
+            where
+                F: FnMut(&str, &'a $($m)? OntologyClass),
+            {
+                match ma.action.$as_ref() {
+                    Some(Action::Procedure(p)) => procedure(p, &format!("{path}/procedure"), f),
+                    Some(Action::Treatment(t)) => {
+                        optional(t.agent.$as_ref(), &format!("{path}/treatment/agent"), f);
+                        optional(t.route_of_administration.$as_ref(), &format!("{path}/treatment/routeOfAdministration"), f);
+                        for (i, di) in t.dose_intervals.$iter().enumerate() {
+                            let di_path = format!("{path}/treatment/doseIntervals/{i}");
+                            if let Some(q) = di.quantity.$as_ref() {
+                                quantity(q, &format!("{di_path}/quantity"), f);
+                            }
+                            optional(di.schedule_frequency.$as_ref(), &format!("{di_path}/scheduleFrequency"), f);
+                        }
+                        if let Some(q) = t.cumulative_dose.$as_ref() {
+                            quantity(q, &format!("{path}/treatment/cumulativeDose"), f);
+                        }
+                    }
+                    Some(Action::RadiationTherapy(rt)) => {
+                        optional(rt.modality.$as_ref(), &format!("{path}/radiationTherapy/modality"), f);
+                        optional(rt.body_site.$as_ref(), &format!("{path}/radiationTherapy/bodySite"), f);
+                    }
+                    Some(Action::TherapeuticRegimen(tr)) => {
+                        if let Some(Identifier::OntologyClass(clz)) = tr.identifier.$as_ref() {
+                            f(&format!("{path}/therapeuticRegimen/ontologyClass"), clz);
+                        }
+                        time_element(tr.start_time.$as_ref(), &format!("{path}/therapeuticRegimen/startTime"), f);
+                        time_element(tr.end_time.$as_ref(), &format!("{path}/therapeuticRegimen/endTime"), f);
+                    }
+                    None => {}
+                }
+                optional(ma.treatment_target.$as_ref(), &format!("{path}/treatmentTarget"), f);
+                optional(ma.treatment_intent.$as_ref(), &format!("{path}/treatmentIntent"), f);
+                optional(ma.response_to_treatment.$as_ref(), &format!("{path}/responseToTreatment"), f);
+                repeated(& $($m)? ma.adverse_events, &format!("{path}/adverseEvents"), f);
+                optional(ma.treatment_termination_reason.$as_ref(), &format!("{path}/treatmentTerminationReason"), f);
+            }
+        }
+    };
+}
+
+define_ontology_class_visitor!(visit, iter, as_ref);
+define_ontology_class_visitor!(visit_mut, iter_mut, as_mut, mut);
+
+
+/// Collect the JSON pointers and references of all OntologyClass messages in the phenopacket.
+pub fn ontology_classes(phenopacket: &Phenopacket) -> Vec<(String, &OntologyClass)> {
+    let mut classes = vec![];
+    visit::phenopacket(phenopacket, &mut |path, clz| classes.push((path.to_string(), clz)));
+    classes
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets::{self, bethlem_interpretation, vsd};
+    use crate::builders::time_elements::ToTimeElement;
+    use crate::constants::onset::CONGENITAL_ONSET;
+    use rstest::rstest;
+
+    fn phenopacket() -> Phenopacket {
+        let mut pp = test_phenopackets::phenopacket();
+        let mut feature = Builder::phenotypic_feature_observed(vsd());
+        feature.onset = Some(CONGENITAL_ONSET.to_time_element());
+        pp.phenotypic_features.push(feature);
+        pp.interpretations.push(bethlem_interpretation());
+        pp
+    }
+
+    #[rstest]
+    fn test_ontology_classes() {
+        let pp = phenopacket();
+        let paths: Vec<(String, String)> = ontology_classes(&pp)
+            .into_iter()
+            .map(|(path, clz)| (path, clz.id.clone()))
+            .collect();
+        assert_eq!(vec![
+            ("/phenotypicFeatures/0/type".to_string(), "HP:0001629".to_string()),
+            ("/phenotypicFeatures/0/onset/ontologyClass".to_string(), "HP:0003577".to_string()),
+            ("/interpretations/0/diagnosis/disease".to_string(), "OMIM:158810".to_string()),
+            ("/interpretations/0/diagnosis/genomicInterpretations/0/variantInterpretation/variationDescriptor/allelicState".to_string(), "GENO:0000135".to_string()),
+        ], paths);
+    }
+
+    #[rstest]
+    fn test_visit_mut() {
+        let mut pp = phenopacket();
+        visit_mut::phenopacket(&mut pp, &mut |_, clz| clz.label = clz.label.to_uppercase());
+        assert_eq!("VENTRICULAR SEPTAL DEFECT", pp.phenotypic_features[0].r#type.as_ref().unwrap().label);
+    }
+}
@@ -0,0 +1,37 @@
+//! Reading of delimited text tables (TSV and CSV)
+use std::io::Read;
+use std::path::Path;
+
+use csv::ReaderBuilder;
+
+use crate::error::{Error, Result};
+
+
+/// The delimiter for a table file: `,` for `.csv` files and a tab otherwise.
+pub fn delimiter_from_path(path: impl AsRef<Path>) -> u8 {
+    match path.as_ref().extension().and_then(|e| e.to_str()) {
+        Some(e) if e.eq_ignore_ascii_case("csv") => b',',
+        _ => b'\t',
+    }
+}
+
+/// Read all rows of a delimited table, including the header rows. Cells are trimmed and rows may have
+/// different lengths.
+pub fn read_rows<R: Read>(reader: R, delimiter: u8) -> Result<Vec<Vec<String>>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| Error::ParseError {
+                msg: format!("Could not read table: {e}"),
+                line: e.position().map(|p| p.line() as usize),
+                column: None,
+            })?;
+            Ok(record.iter().map(|cell| cell.trim().to_string()).collect())
+        })
+        .collect()
+}
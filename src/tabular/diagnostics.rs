@@ -0,0 +1,49 @@
+//! Problems found in the cells of a table
+//!
+//! The importers check every cell and collect all problems, so that a curator can fix a spreadsheet in one pass.
+use std::fmt;
+
+use phenopackets::schema::v2::Phenopacket;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+
+/// A cell whose value could not be converted, e.g., an HPO id that is not a valid CURIE
+/// ([`Error::CurieError`]) or an age that is not an ISO 8601 duration ([`Error::TimeElementError`]).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CellDiagnostic {
+    /// 1-based row number in the file, counting the header rows.
+    pub row: usize,
+    /// Header (label) of the column.
+    pub column: String,
+    pub value: String,
+    pub error: Error,
+}
+
+impl CellDiagnostic {
+    pub fn new(row: usize, column: impl Into<String>, value: impl Into<String>, error: Error) -> Self {
+        CellDiagnostic { row, column: column.into(), value: value.into(), error }
+    }
+}
+
+impl fmt::Display for CellDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Row {}, column '{}', value '{}': {}", self.row, self.column, self.value, self.error)
+    }
+}
+
+
+/// The result of an import: the phenopackets of all rows without problems and the problems of the other rows.
+#[derive(Debug, Default)]
+pub struct TableImport {
+    pub phenopackets: Vec<Phenopacket>,
+    pub diagnostics: Vec<CellDiagnostic>,
+}
+
+impl TableImport {
+    /// Return true if any cell could not be converted.
+    pub fn has_errors(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+}
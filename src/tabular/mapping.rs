@@ -0,0 +1,485 @@
+//! Mapping of arbitrary clinical spreadsheets to phenopackets
+//!
+//! A YAML mapping file declares which column of a table (with one header row and one individual per row)
+//! fills which part of a phenopacket:
+//!
+//! ```yaml
+//! created_by: clinic export
+//! columns:
+//!   - column: Patient
+//!     type: individual_id
+//!   - column: Gender
+//!     type: sex
+//!     values: {M: MALE, F: FEMALE, m: MALE, f: FEMALE}
+//!   - column: Seizures
+//!     type: hpo_term
+//!     id: "HP:0001250"
+//!     label: Seizure
+//!     observed: [yes, y]
+//!     excluded: [no, n]
+//!   - column: Weight (kg)
+//!     type: measurement
+//!     assay: {id: "LOINC:29463-7", label: "Body weight"}
+//!     unit: kilogram
+//!   - column: Calcium (mg/dL)
+//!     type: lab
+//!     assay: {id: "LOINC:17861-6", label: "Calcium [Mass/volume] in Serum or Plasma"}
+//!     unit: milligram_per_deciliter
+//!     low: 8.5
+//!     high: 10.5
+//!     below: {id: "HP:0002901", label: Hypocalcemia}
+//!     above: {id: "HP:0003072", label: Hypercalcemia}
+//!   - column: Age
+//!     type: age
+//! ```
+//!
+//! The sex values are the names of the [`Sex`] enum. The unit is the name of a [`Unit`] function or a unit id.
+//! The age (parsed with [`time_element_from_str`]) is the age at the last encounter. Empty cells are skipped.
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use phenopackets::schema::v2::core::{Individual, Measurement, OntologyClass, PhenotypicFeature, Sex};
+use phenopackets::schema::v2::Phenopacket;
+use serde::{Deserialize, Serialize};
+
+use crate::builders::builder::{curie_prefix, Builder};
+use crate::builders::measurement_builder::MeasurementBuilder;
+use crate::builders::phenopacket_builder::PhenopacketBuilder;
+use crate::builders::time_elements::time_element_from_str;
+use crate::constants::units::Unit;
+use crate::error::{Error, Result};
+use crate::tabular::delimited::{delimiter_from_path, read_rows};
+use crate::tabular::diagnostics::{CellDiagnostic, TableImport};
+use crate::tabular::threshold::ThresholdMapper;
+
+
+impl Error {
+    fn invalid_mapping(column: &str, reason: impl std::fmt::Display) -> Self {
+        Error::ParseError { msg: format!("Invalid mapping of column '{column}': {reason}"), line: None, column: None }
+    }
+
+    fn unmapped_sex(value: &str) -> Self {
+        Error::IndividualError { msg: format!("Unknown sex '{value}'") }
+    }
+
+    fn not_in_vocabulary(term: &OntologyClass, value: &str) -> Self {
+        Error::OntologyError { msg: format!("'{value}' is not in the observed/excluded vocabulary of {}", term.label) }
+    }
+}
+
+
+/// The mapping of the columns of a table to phenopacket fields, usually read from YAML.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappingConfig {
+    /// Written to `MetaData.created_by` of the phenopackets.
+    #[serde(default = "default_created_by")]
+    pub created_by: String,
+    pub columns: Vec<ColumnMapping>,
+}
+
+impl MappingConfig {
+    pub fn from_yaml_str(yaml: &str) -> Result<Self> {
+        serde_yaml::from_str(yaml).map_err(|e| {
+            let location = e.location();
+            Error::ParseError {
+                msg: format!("Could not parse mapping: {e}"),
+                line: location.as_ref().map(|l| l.line()),
+                column: location.as_ref().map(|l| l.column()),
+            }
+        })
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let yaml = fs::read_to_string(path).map_err(|e| Error::io(format!("Could not read {}: {e}", path.display())))?;
+        Self::from_yaml_str(&yaml)
+    }
+}
+
+fn default_created_by() -> String {
+    "anonymous biocurator".to_string()
+}
+
+/// The phenopacket field filled from one column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    /// The header of the column.
+    pub column: String,
+    #[serde(flatten)]
+    pub target: MappingTarget,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MappingTarget {
+    /// The id of the individual, which is also the id of the phenopacket. Exactly one column must have this type.
+    IndividualId,
+    /// The sex of the individual. `values` maps the cell values to the names of [`Sex`] values.
+    Sex { values: BTreeMap<String, String> },
+    /// An HPO term that is observed or excluded according to the (case-insensitive) vocabulary.
+    HpoTerm {
+        id: String,
+        label: String,
+        #[serde(default = "default_observed")]
+        observed: Vec<String>,
+        #[serde(default = "default_excluded")]
+        excluded: Vec<String>,
+    },
+    /// A numeric measurement of the assay (a LOINC term) in the unit.
+    Measurement { assay: OntologyClass, unit: String },
+    /// A lab value, recorded as a measurement with the reference range `low..=high` and the HPO term for values
+    /// below or above the range (see [`ThresholdMapper`]).
+    Lab {
+        assay: OntologyClass,
+        unit: String,
+        low: f64,
+        high: f64,
+        below: Option<OntologyClass>,
+        above: Option<OntologyClass>,
+    },
+    /// The age at the last encounter.
+    Age,
+}
+
+fn default_observed() -> Vec<String> {
+    vec!["yes".to_string()]
+}
+
+fn default_excluded() -> Vec<String> {
+    vec!["no".to_string()]
+}
+
+
+/// Applies a [`MappingConfig`] to each row of a table.
+///
+/// The mapping is checked when the mapper is created; problems with cells are collected as [`CellDiagnostic`]s.
+///
+/// ```no_run
+/// use phenopacket_tools::tabular::mapping::{MappingConfig, TableMapper};
+///
+/// let mapper = TableMapper::new(MappingConfig::from_path("mapping.yaml").unwrap()).unwrap();
+/// let import = mapper.read_path("export.csv").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableMapper {
+    created_by: String,
+    rules: Vec<(String, Rule)>,
+}
+
+/// A validated mapping target.
+#[derive(Debug, Clone)]
+enum Rule {
+    IndividualId,
+    Sex(HashMap<String, Sex>),
+    HpoTerm { term: OntologyClass, observed: Vec<String>, excluded: Vec<String> },
+    Measurement { assay: OntologyClass, unit: OntologyClass },
+    Lab(ThresholdMapper),
+    Age,
+}
+
+impl TableMapper {
+    /// Check the mapping: there must be one individual id column, the sex values must be names of [`Sex`] values,
+    /// the HPO and LOINC ids must be valid, and the units must be known.
+    pub fn new(config: MappingConfig) -> Result<Self> {
+        let id_columns = config.columns.iter().filter(|c| c.target == MappingTarget::IndividualId).count();
+        if id_columns != 1 {
+            return Err(Error::ParseError {
+                msg: format!("The mapping must have one individual_id column but has {id_columns}"),
+                line: None,
+                column: None,
+            });
+        }
+        let rules = config.columns
+            .into_iter()
+            .map(|mapping| {
+                let rule = Rule::new(mapping.target).map_err(|e| Error::invalid_mapping(&mapping.column, e))?;
+                Ok((mapping.column, rule))
+            })
+            .collect::<Result<_>>()?;
+        Ok(TableMapper { created_by: config.created_by, rules })
+    }
+
+    /// Read a table file. Files with the extension `.csv` are comma-separated, all others tab-separated.
+    pub fn read_path(&self, path: impl AsRef<Path>) -> Result<TableImport> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::io(format!("Could not open {}: {e}", path.display())))?;
+        self.read(BufReader::new(file), delimiter_from_path(path))
+    }
+
+    /// Read a table with the given delimiter.
+    pub fn read<R: Read>(&self, reader: R, delimiter: u8) -> Result<TableImport> {
+        let rows = read_rows(reader, delimiter)?;
+        let Some(header) = rows.first() else {
+            return Ok(TableImport::default());
+        };
+        let mut indices = Vec::with_capacity(self.rules.len());
+        let mut missing = vec![];
+        for (column, _) in &self.rules {
+            match header.iter().position(|h| h == column) {
+                Some(i) => indices.push(i),
+                None => missing.push(column.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(Error::ParseError {
+                msg: format!("Table is missing the mapped columns: {}", missing.join(", ")),
+                line: Some(1),
+                column: None,
+            });
+        }
+        let mut import = TableImport::default();
+        for (i, cells) in rows.iter().enumerate().skip(1) {
+            if cells.iter().all(|c| c.is_empty()) {
+                continue;
+            }
+            let errors_before = import.diagnostics.len();
+            let mut case = Case::default();
+            for ((column, rule), &index) in self.rules.iter().zip(&indices) {
+                let value = cells.get(index).map_or("", String::as_str);
+                if let Err(e) = rule.apply(value, &mut case) {
+                    import.diagnostics.push(CellDiagnostic::new(i + 1, column, value, e));
+                }
+            }
+            if import.diagnostics.len() == errors_before {
+                import.phenopackets.push(case.phenopacket(&self.created_by)?);
+            }
+        }
+        Ok(import)
+    }
+}
+
+impl Rule {
+    fn new(target: MappingTarget) -> Result<Self> {
+        match target {
+            MappingTarget::IndividualId => Ok(Rule::IndividualId),
+            MappingTarget::Sex { values } => {
+                let values = values
+                    .into_iter()
+                    .map(|(value, name)| match Sex::from_str_name(&name) {
+                        Some(sex) => Ok((value, sex)),
+                        None => Err(Error::unmapped_sex(&name)),
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(Rule::Sex(values))
+            }
+            MappingTarget::HpoTerm { id, label, observed, excluded } => {
+                Ok(Rule::HpoTerm { term: hpo_term(id, label)?, observed, excluded })
+            }
+            MappingTarget::Measurement { assay, unit } => {
+                let assay = Builder::ontology_class(assay.id, assay.label)?;
+                Ok(Rule::Measurement { assay, unit: unit_from_name(&unit)? })
+            }
+            MappingTarget::Lab { assay, unit, low, high, below, above } => {
+                let assay = Builder::ontology_class(assay.id, assay.label)?;
+                let mut mapper = ThresholdMapper::new(assay, unit_from_name(&unit)?, low, high)?;
+                if let Some(term) = below {
+                    mapper = mapper.below(hpo_term(term.id, term.label)?);
+                }
+                if let Some(term) = above {
+                    mapper = mapper.above(hpo_term(term.id, term.label)?);
+                }
+                Ok(Rule::Lab(mapper))
+            }
+            MappingTarget::Age => Ok(Rule::Age),
+        }
+    }
+
+    fn apply(&self, value: &str, case: &mut Case) -> Result<()> {
+        if value.is_empty() {
+            return match self {
+                Rule::IndividualId => Err(Error::IndividualError { msg: "Missing individual id".to_string() }),
+                _ => Ok(()),
+            };
+        }
+        match self {
+            Rule::IndividualId => case.individual.id = value.to_string(),
+            Rule::Sex(values) => {
+                let sex = values.get(value).ok_or_else(|| Error::unmapped_sex(value))?;
+                case.individual.set_sex(*sex);
+            }
+            Rule::HpoTerm { term, observed, excluded } => {
+                let matches = |vocabulary: &[String]| vocabulary.iter().any(|v| v.eq_ignore_ascii_case(value));
+                let feature = if matches(observed) {
+                    Builder::phenotypic_feature_observed(term.clone())
+                } else if matches(excluded) {
+                    Builder::phenotypic_feature_excluded(term.clone())
+                } else {
+                    return Err(Error::not_in_vocabulary(term, value));
+                };
+                case.features.push(feature);
+            }
+            Rule::Measurement { assay, unit } => {
+                let measurement = MeasurementBuilder::new(assay.clone()).quantity(parse_number(value)?, unit.clone()).build()?;
+                case.measurements.push(measurement);
+            }
+            Rule::Lab(mapper) => {
+                let number = parse_number(value)?;
+                case.measurements.push(mapper.measurement(number));
+                case.features.extend(mapper.phenotypic_features(number));
+            }
+            Rule::Age => case.individual.time_at_last_encounter = Some(time_element_from_str(value)?),
+        }
+        Ok(())
+    }
+}
+
+fn hpo_term(id: String, label: String) -> Result<OntologyClass> {
+    if curie_prefix(&id) != Some("HP") {
+        return Err(Error::CurieError { msg: format!("Not an HPO id: '{id}'") });
+    }
+    Builder::ontology_class(id, label)
+}
+
+fn unit_from_name(name: &str) -> Result<OntologyClass> {
+    Unit::from_name(name).ok_or_else(|| Error::MeasurementError { msg: format!("Unknown unit '{name}'") })
+}
+
+/// Parse a finite number. `NaN` and infinity are rejected because they cannot be written to JSON.
+fn parse_number(value: &str) -> Result<f64> {
+    value
+        .parse()
+        .ok()
+        .filter(|n: &f64| n.is_finite())
+        .ok_or_else(|| Error::MeasurementError { msg: format!("Not a number: '{value}'") })
+}
+
+/// The parts of a phenopacket collected from the cells of a row.
+struct Case {
+    individual: Individual,
+    features: Vec<PhenotypicFeature>,
+    measurements: Vec<Measurement>,
+}
+
+impl Default for Case {
+    fn default() -> Self {
+        Case { individual: Builder::individual(""), features: vec![], measurements: vec![] }
+    }
+}
+
+impl Case {
+    fn phenopacket(self, created_by: &str) -> Result<Phenopacket> {
+        let mut builder = PhenopacketBuilder::new(&self.individual.id)
+            .subject(self.individual)
+            .meta_data(Builder::meta_data_now(created_by))
+            .phenotypic_features(self.features);
+        for measurement in self.measurements {
+            builder = builder.measurement(measurement);
+        }
+        builder.build()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    const MAPPING: &str = r#"
+columns:
+  - column: Patient
+    type: individual_id
+  - column: Gender
+    type: sex
+    values: {M: MALE, F: FEMALE, m: MALE, f: FEMALE}
+  - column: Seizures
+    type: hpo_term
+    id: "HP:0001250"
+    label: Seizure
+    observed: [yes, y, "1"]
+    excluded: [no, n, "0"]
+  - column: Calcium
+    type: measurement
+    assay: {id: "LOINC:17861-6", label: "Calcium [Mass/volume] in Serum or Plasma"}
+    unit: milligram_per_deciliter
+  - column: Potassium
+    type: lab
+    assay: {id: "LOINC:2823-3", label: "Potassium [Moles/volume] in Serum or Plasma"}
+    unit: mole_per_liter
+    low: 0.0035
+    high: 0.0051
+    below: {id: "HP:0002900", label: Hypokalemia}
+  - column: Age
+    type: age
+"#;
+
+    #[fixture]
+    fn mapper() -> TableMapper {
+        TableMapper::new(MappingConfig::from_yaml_str(MAPPING).unwrap()).unwrap()
+    }
+
+    #[rstest]
+    fn test_map_rows(mapper: TableMapper) {
+        let table = "Patient,Age,Gender,Seizures,Calcium,Potassium,Notes\nA1,P3Y,f,Y,9.5,0.0041,x\nA2,,M,0,,0.0029,\n";
+        let import = mapper.read(table.as_bytes(), b',').unwrap();
+        assert!(!import.has_errors());
+        let [a1, a2] = import.phenopackets.as_slice() else { panic!("expected two phenopackets") };
+        assert_eq!("A1", a1.id);
+        let subject = a1.subject.as_ref().unwrap();
+        assert_eq!(Sex::Female, subject.sex());
+        assert!(subject.time_at_last_encounter.is_some());
+        assert_eq!(2, a1.measurements.len());
+        assert_eq!(vec![false, true], a1.phenotypic_features.iter().map(|f| f.excluded).collect::<Vec<_>>());
+        let calcium = Builder::ontology_class("LOINC:17861-6", "Calcium [Mass/volume] in Serum or Plasma").unwrap();
+        let expected = MeasurementBuilder::new(calcium).quantity(9.5, Unit::milligram_per_deciliter()).build().unwrap();
+        assert_eq!(expected, a1.measurements[0]);
+        assert_eq!(vec![true, false], a2.phenotypic_features.iter().map(|f| f.excluded).collect::<Vec<_>>());
+        assert_eq!("Hypokalemia", a2.phenotypic_features[1].r#type.as_ref().unwrap().label);
+        assert_eq!(1, a2.measurements.len());
+        assert_eq!("anonymous biocurator", a2.meta_data.as_ref().unwrap().created_by);
+    }
+
+    #[rstest]
+    fn test_diagnostics(mapper: TableMapper) {
+        let table = "Patient\tGender\tSeizures\tCalcium\tPotassium\tAge\nA1\tX\tmaybe\tlow\t4.1 mmol/L\tP6Y3\n\tF\tyes\t9\t\tP1Y\n";
+        let import = mapper.read(table.as_bytes(), b'\t').unwrap();
+        assert!(import.phenopackets.is_empty());
+        let columns: Vec<(usize, &str)> = import.diagnostics.iter().map(|d| (d.row, d.column.as_str())).collect();
+        assert_eq!(vec![(2, "Gender"), (2, "Seizures"), (2, "Calcium"), (2, "Potassium"), (2, "Age"), (3, "Patient")],
+            columns);
+        assert!(matches!(import.diagnostics[3].error, Error::MeasurementError { .. }));
+        assert!(matches!(import.diagnostics[4].error, Error::TimeElementError { .. }));
+    }
+
+    #[rstest]
+    #[case("NaN")]
+    #[case("inf")]
+    #[case("-Infinity")]
+    fn test_non_finite_number(#[case] value: &str) {
+        let mapping = r#"
+columns:
+  - column: Patient
+    type: individual_id
+  - column: Calcium
+    type: measurement
+    assay: {id: "LOINC:17861-6", label: "Calcium [Mass/volume] in Serum or Plasma"}
+    unit: milligram_per_deciliter
+"#;
+        let mapper = TableMapper::new(MappingConfig::from_yaml_str(mapping).unwrap()).unwrap();
+        let table = format!("Patient,Calcium\nA1,{value}\n");
+        let import = mapper.read(table.as_bytes(), b',').unwrap();
+        assert!(import.phenopackets.is_empty());
+        let [diagnostic] = import.diagnostics.as_slice() else { panic!("expected one diagnostic") };
+        assert_eq!("Calcium", diagnostic.column);
+        assert_eq!(format!("Not a number: '{value}'"), diagnostic.error.to_string());
+    }
+
+    #[rstest]
+    #[case("unit: milligram_per_deciliter", "unit: furlong", "Unknown unit 'furlong'")]
+    #[case("values: {M: MALE", "values: {M: MAN", "Unknown sex 'MAN'")]
+    #[case("id: \"HP:0001250\"", "id: \"HP:1250\"", "HP:1250")]
+    fn test_invalid_mapping(#[case] from: &str, #[case] to: &str, #[case] reason: &str) {
+        let config = MappingConfig::from_yaml_str(&MAPPING.replace(from, to)).unwrap();
+        let error = TableMapper::new(config).unwrap_err().to_string();
+        assert!(error.starts_with("Invalid mapping of column"), "{error}");
+        assert!(error.contains(reason), "{error}");
+    }
+
+    #[rstest]
+    fn test_missing_column(mapper: TableMapper) {
+        let error = mapper.read("Patient,Gender\nA1,M\n".as_bytes(), b',').unwrap_err();
+        assert_eq!("Table is missing the mapped columns: Seizures, Calcium, Potassium, Age", error.to_string());
+    }
+}
@@ -0,0 +1,11 @@
+//! Generation of phenopackets from tabular data
+//!
+//! Case templates in the pyphetools layout are read with [`template::TemplateReader`], which reports problems
+//! with individual cells as [`diagnostics::CellDiagnostic`]s. Other spreadsheets are converted according to a
+//! column mapping with [`mapping::TableMapper`]; lab values are turned into measurements and HPO features by
+//! [`threshold::ThresholdMapper`].
+pub mod delimited;
+pub mod diagnostics;
+pub mod mapping;
+pub mod template;
+pub mod threshold;
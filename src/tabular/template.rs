@@ -0,0 +1,468 @@
+//! Import of case templates in the pyphetools layout
+//!
+//! A template is a delimited table (tab-separated, or comma-separated for `.csv` files) with one case per row
+//! and two header rows. The first header row has the column labels and the second the HPO ids of the phenotype
+//! columns (fixed columns have a type hint such as `CURIE` or `str` there, which is ignored):
+//!
+//! ```text
+//! PMID        title   individual_id  disease_id   ...  sex      HPO  Seizure     Ataxia
+//! CURIE       str     str            CURIE        ...  M:F:O:U  na   HP:0001250  HP:0001251
+//! PMID:12345  A case  P1             OMIM:620000  ...  M        na   observed    P2Y
+//! ```
+//!
+//! The fixed columns ([`FIXED_COLUMNS`]) may come in any order and further columns (e.g., `comment`) are ignored.
+//! The phenotype columns are the columns after the `HPO` column or, without it, after the last fixed column.
+//! Their cells are `observed`, `excluded`, `na` (not assessed), or an onset (an ISO 8601 age, a gestational age,
+//! or the label of an HPO onset term), which means that the feature was observed with this onset.
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use phenopackets::ga4gh::vrsatile::v1::{GeneDescriptor, VariationDescriptor};
+use phenopackets::schema::v2::core::genomic_interpretation::InterpretationStatus;
+use phenopackets::schema::v2::core::{
+    AcmgPathogenicityClassification, Diagnosis, GenomicInterpretation, Interpretation, OntologyClass,
+    PhenotypicFeature, Sex, TimeElement,
+};
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::builders::builder::{check_valid_curie, curie_prefix, Builder};
+use crate::builders::expressions::Expressions;
+use crate::builders::phenopacket_builder::PhenopacketBuilder;
+use crate::builders::time_elements::time_element_from_str;
+use crate::constants::allelic_state::AllelicState;
+use crate::error::{Error, Result};
+use crate::tabular::delimited::{delimiter_from_path, read_rows};
+use crate::tabular::diagnostics::{CellDiagnostic, TableImport};
+
+pub const PMID: &str = "PMID";
+pub const TITLE: &str = "title";
+pub const INDIVIDUAL_ID: &str = "individual_id";
+pub const DISEASE_ID: &str = "disease_id";
+pub const DISEASE_LABEL: &str = "disease_label";
+pub const HGNC_ID: &str = "HGNC_id";
+pub const GENE_SYMBOL: &str = "gene_symbol";
+pub const TRANSCRIPT: &str = "transcript";
+pub const ALLELE_1: &str = "allele_1";
+pub const ALLELE_2: &str = "allele_2";
+pub const AGE_OF_ONSET: &str = "age_of_onset";
+pub const AGE_AT_LAST_ENCOUNTER: &str = "age_at_last_encounter";
+pub const SEX: &str = "sex";
+
+/// The columns that every template must have.
+pub const FIXED_COLUMNS: [&str; 13] = [
+    PMID, TITLE, INDIVIDUAL_ID, DISEASE_ID, DISEASE_LABEL, HGNC_ID, GENE_SYMBOL, TRANSCRIPT, ALLELE_1, ALLELE_2,
+    AGE_OF_ONSET, AGE_AT_LAST_ENCOUNTER, SEX,
+];
+
+/// The column that separates the fixed columns from the phenotype columns.
+const HPO_SEPARATOR: &str = "HPO";
+
+/// Number of header rows (labels and HPO ids).
+const HEADER_ROWS: usize = 2;
+
+
+impl Error {
+    fn template_header(msg: impl Into<String>) -> Self {
+        Error::ParseError { msg: msg.into(), line: Some(1), column: None }
+    }
+
+    fn unknown_sex(value: &str) -> Self {
+        Error::IndividualError { msg: format!("Unknown sex '{value}' (expected M, F, O, or U)") }
+    }
+
+    fn unknown_phenotype_value(value: &str, error: Error) -> Self {
+        Error::TimeElementError {
+            msg: format!("Expected observed, excluded, na, or an onset but got '{value}' ({error})"),
+        }
+    }
+}
+
+
+/// Create one phenopacket per row of a pyphetools-style template.
+///
+/// The phenopacket id is derived from the PMID and the individual id (e.g., `PMID_12345_P1`). The MetaData has
+/// the publication as external reference; Resources can be added with
+/// [`crate::builders::resource_registry::ResourceRegistry::populate_resources`].
+///
+/// Every cell is checked and all problems are returned as [`CellDiagnostic`]s; rows with problems do not yield
+/// a phenopacket. Only a template that cannot be read or lacks fixed columns is an error.
+///
+/// ```no_run
+/// use phenopacket_tools::tabular::template::TemplateReader;
+///
+/// let import = TemplateReader::new("anonymous biocurator").read_path("template.tsv").unwrap();
+/// for diagnostic in &import.diagnostics {
+///     eprintln!("{diagnostic}");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TemplateReader {
+    created_by: String,
+}
+
+impl TemplateReader {
+    /// A reader that records `created_by` in the MetaData of the phenopackets.
+    pub fn new(created_by: impl Into<String>) -> Self {
+        TemplateReader { created_by: created_by.into() }
+    }
+
+    /// Read a template file. Files with the extension `.csv` are comma-separated, all others tab-separated.
+    pub fn read_path(&self, path: impl AsRef<Path>) -> Result<TableImport> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::io(format!("Could not open {}: {e}", path.display())))?;
+        self.read(BufReader::new(file), delimiter_from_path(path))
+    }
+
+    /// Read a template with the given delimiter.
+    pub fn read<R: Read>(&self, reader: R, delimiter: u8) -> Result<TableImport> {
+        let rows = read_rows(reader, delimiter)?;
+        if rows.len() < HEADER_ROWS {
+            return Err(Error::template_header("A template needs two header rows (labels and HPO ids)"));
+        }
+        let mut import = TableImport::default();
+        let layout = Layout::new(&rows[0], &rows[1], &mut import.diagnostics)?;
+        for (i, cells) in rows.iter().enumerate().skip(HEADER_ROWS) {
+            if cells.iter().all(|c| c.is_empty()) {
+                continue;
+            }
+            let row = Row { number: i + 1, cells, layout: &layout };
+            if let Some(phenopacket) = self.phenopacket(&row, &mut import.diagnostics) {
+                import.phenopackets.push(phenopacket);
+            }
+        }
+        Ok(import)
+    }
+
+    /// Convert a row, or return None and add the problems of the row to the diagnostics.
+    fn phenopacket(&self, row: &Row, diagnostics: &mut Vec<CellDiagnostic>) -> Option<Phenopacket> {
+        let pmid = row.parse(PMID, diagnostics, |v| check_valid_curie(v).map(|_| v.to_string()));
+        let individual_id = row.parse(INDIVIDUAL_ID, diagnostics, |v| match v {
+            "" => Err(Error::IndividualError { msg: "Missing individual id".to_string() }),
+            v => Ok(v.to_string()),
+        });
+        let disease = row.parse(DISEASE_ID, diagnostics, |v| Builder::ontology_class(v, row.get(DISEASE_LABEL)));
+        let gene = row.parse(HGNC_ID, diagnostics, |v| match v {
+            v if is_na(v) => Ok(None),
+            v => check_valid_curie(v).map(|_| Some(Builder::gene_descriptor(v, row.get(GENE_SYMBOL)))),
+        });
+        let sex = row.parse(SEX, diagnostics, parse_sex);
+        let onset = row.parse(AGE_OF_ONSET, diagnostics, optional_time_element);
+        let last_encounter = row.parse(AGE_AT_LAST_ENCOUNTER, diagnostics, optional_time_element);
+        let features: Vec<Option<PhenotypicFeature>> = row.layout.phenotypes
+            .iter()
+            .filter_map(|(column, term)| row.parse_at(*column, &term.label, diagnostics, |v| phenotypic_feature(term, v)))
+            .collect();
+        let (Some(pmid), Some(individual_id), Some(disease), Some(gene), Some(sex), Some(onset), Some(last_encounter)) =
+            (pmid, individual_id, disease, gene, sex, onset, last_encounter)
+        else {
+            return None;
+        };
+        if features.len() < row.layout.phenotypes.len() {
+            return None;
+        }
+
+        let mut subject = Builder::individual(&individual_id);
+        subject.set_sex(sex);
+        subject.time_at_last_encounter = last_encounter;
+        let mut meta_data = Builder::meta_data_now(&self.created_by);
+        meta_data.external_references.push(Builder::external_reference(&pmid, row.get(TITLE)));
+        let id = phenopacket_id(&pmid, &individual_id);
+        let mut builder = PhenopacketBuilder::new(&id)
+            .subject(subject)
+            .meta_data(meta_data)
+            .phenotypic_features(features.into_iter().flatten());
+        if let Some(interpretation) = interpretation(&id, &individual_id, &disease, gene, row) {
+            builder = builder.interpretation(interpretation);
+        }
+        let mut disease = Builder::disease(disease);
+        disease.onset = onset;
+        builder
+            .disease(disease)
+            .build()
+            .map_err(|e| diagnostics.push(CellDiagnostic::new(row.number, INDIVIDUAL_ID, &individual_id, e)))
+            .ok()
+    }
+}
+
+
+/// Column indices of the fixed columns and the phenotype columns with their HPO term.
+struct Layout {
+    fixed: HashMap<&'static str, usize>,
+    phenotypes: Vec<(usize, OntologyClass)>,
+}
+
+impl Layout {
+    /// Find the columns. Phenotype columns with an invalid HPO id are reported and skipped.
+    fn new(labels: &[String], ids: &[String], diagnostics: &mut Vec<CellDiagnostic>) -> Result<Self> {
+        let fixed: HashMap<&'static str, usize> = FIXED_COLUMNS
+            .iter()
+            .filter_map(|&name| labels.iter().position(|l| l == name).map(|i| (name, i)))
+            .collect();
+        let missing: Vec<&str> = FIXED_COLUMNS.into_iter().filter(|name| !fixed.contains_key(name)).collect();
+        if !missing.is_empty() {
+            return Err(Error::template_header(format!("Template is missing the columns: {}", missing.join(", "))));
+        }
+        let first = match labels.iter().position(|l| l == HPO_SEPARATOR) {
+            Some(separator) => separator + 1,
+            None => fixed.values().max().map_or(0, |last| last + 1),
+        };
+        let mut phenotypes = vec![];
+        for (column, label) in labels.iter().enumerate().skip(first) {
+            let id = ids.get(column).map_or("", String::as_str);
+            if label.is_empty() && id.is_empty() {
+                continue;
+            }
+            match hpo_term(id, label) {
+                Ok(term) => phenotypes.push((column, term)),
+                Err(e) => diagnostics.push(CellDiagnostic::new(HEADER_ROWS, label, id, e)),
+            }
+        }
+        Ok(Layout { fixed, phenotypes })
+    }
+}
+
+/// The cells of a data row. `number` is the 1-based row number in the file.
+struct Row<'a> {
+    number: usize,
+    cells: &'a [String],
+    layout: &'a Layout,
+}
+
+impl Row<'_> {
+    /// The value of a fixed column.
+    fn get(&self, column: &str) -> &str {
+        self.layout.fixed.get(column).map_or("", |&i| self.cell(i))
+    }
+
+    fn cell(&self, column: usize) -> &str {
+        self.cells.get(column).map_or("", String::as_str)
+    }
+
+    /// Parse the value of a fixed column. Errors are added to the diagnostics.
+    fn parse<T>(
+        &self,
+        column: &str,
+        diagnostics: &mut Vec<CellDiagnostic>,
+        parse: impl FnOnce(&str) -> Result<T>,
+    ) -> Option<T> {
+        self.parse_at(self.layout.fixed[column], column, diagnostics, parse)
+    }
+
+    fn parse_at<T>(
+        &self,
+        column: usize,
+        header: &str,
+        diagnostics: &mut Vec<CellDiagnostic>,
+        parse: impl FnOnce(&str) -> Result<T>,
+    ) -> Option<T> {
+        let value = self.cell(column);
+        parse(value)
+            .map_err(|e| diagnostics.push(CellDiagnostic::new(self.number, header, value, e)))
+            .ok()
+    }
+}
+
+fn is_na(value: &str) -> bool {
+    value.is_empty() || value.eq_ignore_ascii_case("na")
+}
+
+fn hpo_term(id: &str, label: &str) -> Result<OntologyClass> {
+    if curie_prefix(id) != Some("HP") {
+        return Err(Error::CurieError { msg: format!("Not an HPO id: '{id}'") });
+    }
+    Builder::ontology_class(id, label)
+}
+
+fn parse_sex(value: &str) -> Result<Sex> {
+    match value.to_ascii_uppercase().as_str() {
+        "M" | "MALE" => Ok(Sex::Male),
+        "F" | "FEMALE" => Ok(Sex::Female),
+        "O" | "OTHER" => Ok(Sex::OtherSex),
+        "U" | "UNKNOWN" | "NA" | "" => Ok(Sex::UnknownSex),
+        _ => Err(Error::unknown_sex(value)),
+    }
+}
+
+fn optional_time_element(value: &str) -> Result<Option<TimeElement>> {
+    if is_na(value) {
+        Ok(None)
+    } else {
+        time_element_from_str(value).map(Some)
+    }
+}
+
+fn phenotypic_feature(term: &OntologyClass, value: &str) -> Result<Option<PhenotypicFeature>> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "na" => Ok(None),
+        "observed" => Ok(Some(Builder::phenotypic_feature_observed(term.clone()))),
+        "excluded" => Ok(Some(Builder::phenotypic_feature_excluded(term.clone()))),
+        _ => {
+            let onset = time_element_from_str(value).map_err(|e| Error::unknown_phenotype_value(value, e))?;
+            let mut feature = Builder::phenotypic_feature_observed(term.clone());
+            feature.onset = Some(onset);
+            Ok(Some(feature))
+        }
+    }
+}
+
+/// The causative gene or variants. Identical alleles are taken to be homozygous, different ones heterozygous.
+fn interpretation(
+    id: &str,
+    individual_id: &str,
+    disease: &OntologyClass,
+    gene: Option<GeneDescriptor>,
+    row: &Row,
+) -> Option<Interpretation> {
+    let allele_1 = row.get(ALLELE_1);
+    let allele_2 = row.get(ALLELE_2);
+    let alleles: Vec<(&str, OntologyClass)> = if is_na(allele_1) {
+        vec![]
+    } else if allele_1 == allele_2 {
+        vec![(allele_1, AllelicState::homozygous())]
+    } else if is_na(allele_2) {
+        vec![(allele_1, AllelicState::heterozygous())]
+    } else {
+        vec![(allele_1, AllelicState::heterozygous()), (allele_2, AllelicState::heterozygous())]
+    };
+    let genomic_interpretations: Vec<GenomicInterpretation> = if alleles.is_empty() {
+        let gene = gene?;
+        vec![Builder::genomic_interpretation_from_gene_descriptor(individual_id, InterpretationStatus::Causative, gene)]
+    } else {
+        alleles
+            .into_iter()
+            .enumerate()
+            .map(|(i, (allele, allelic_state))| {
+                let mut vd = variation_descriptor(format!("{id}-allele-{}", i + 1), row.get(TRANSCRIPT), allele);
+                vd.gene_context = gene.clone();
+                vd.allelic_state = Some(allelic_state);
+                let vi = Builder::variant_interpretation_acmg(AcmgPathogenicityClassification::NotProvided, vd);
+                Builder::genomic_interpretation_from_variant(individual_id, InterpretationStatus::Causative, vi)
+            })
+            .collect()
+    };
+    let diagnosis = Diagnosis { disease: Some(disease.clone()), genomic_interpretations };
+    Some(Builder::solved_interpretation(format!("{id}-interpretation"), diagnosis))
+}
+
+/// HGVS cDNA alleles (`c.`) become an expression on the transcript; other alleles (e.g., structural variants
+/// such as `DEL exon 5`) are kept as the label.
+fn variation_descriptor(id: String, transcript: &str, allele: &str) -> VariationDescriptor {
+    let mut vd = Builder::variation_descriptor(id);
+    if allele.starts_with("c.") {
+        let hgvs = if transcript.is_empty() { allele.to_string() } else { format!("{transcript}:{allele}") };
+        vd.expressions.push(Expressions::hgvs_cdna(hgvs));
+    } else {
+        vd.label = allele.to_string();
+    }
+    vd
+}
+
+/// The phenopacket id in the pyphetools convention, with all characters other than letters and digits replaced
+/// by `_`, e.g., `PMID_12345_P_1` for `PMID:12345` and `P 1`.
+fn phenopacket_id(pmid: &str, individual_id: &str) -> String {
+    format!("{pmid}_{individual_id}")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phenopackets::schema::v2::core::genomic_interpretation::Call;
+    use phenopackets::schema::v2::core::time_element::Element;
+    use rstest::{fixture, rstest};
+
+    const HEADER: &str = "PMID\ttitle\tindividual_id\tcomment\tdisease_id\tdisease_label\tHGNC_id\tgene_symbol\ttranscript\tallele_1\tallele_2\tage_of_onset\tage_at_last_encounter\tsex\tHPO\tSeizure\tAtaxia\n\
+        CURIE\tstr\tstr\toptional str\tCURIE\tstr\tCURIE\tstr\tstr\tstr\tstr\tage\tage\tM:F:O:U\tna\tHP:0001250\tHP:0001251\n";
+
+    #[fixture]
+    fn template() -> String {
+        format!("{HEADER}\
+            PMID:12345\tA new syndrome\tP1\t\tOMIM:620000\tNew syndrome\tHGNC:3603\tFBN1\tNM_000138.5\tc.123A>G\tna\tInfantile onset\tP6Y\tM\tna\tobserved\tP2Y\n\
+            PMID:12345\tA new syndrome\tP 2\t\tOMIM:620000\tNew syndrome\tHGNC:3603\tFBN1\tNM_000138.5\tc.123A>G\tc.123A>G\tna\tna\tf\tna\texcluded\tna\n")
+    }
+
+    #[rstest]
+    fn test_read_template(template: String) {
+        let import = TemplateReader::new("anonymous biocurator").read(template.as_bytes(), b'\t').unwrap();
+        assert!(!import.has_errors());
+        let phenopackets = import.phenopackets;
+        assert_eq!(2, phenopackets.len());
+        let pp = &phenopackets[0];
+        assert_eq!("PMID_12345_P1", pp.id);
+        let subject = pp.subject.as_ref().unwrap();
+        assert_eq!(Sex::Male, subject.sex());
+        assert!(subject.time_at_last_encounter.is_some());
+        assert_eq!(2, pp.phenotypic_features.len());
+        let ataxia = &pp.phenotypic_features[1];
+        assert_eq!("Ataxia", ataxia.r#type.as_ref().unwrap().label);
+        assert!(matches!(ataxia.onset.as_ref().unwrap().element, Some(Element::Age(_))));
+        let disease = &pp.diseases[0];
+        assert_eq!("OMIM:620000", disease.term.as_ref().unwrap().id);
+        assert!(matches!(disease.onset.as_ref().unwrap().element, Some(Element::OntologyClass(_))));
+        assert_eq!("PMID:12345", pp.meta_data.as_ref().unwrap().external_references[0].id);
+    }
+
+    #[rstest]
+    fn test_variants(template: String) {
+        let phenopackets = TemplateReader::new("anonymous biocurator").read(template.as_bytes(), b'\t').unwrap().phenopackets;
+        let variants = |pp: &Phenopacket| -> Vec<VariationDescriptor> {
+            pp.interpretations[0].diagnosis.as_ref().unwrap().genomic_interpretations.iter()
+                .map(|gi| match &gi.call {
+                    Some(Call::VariantInterpretation(vi)) => vi.variation_descriptor.clone().unwrap(),
+                    _ => panic!("expected a variant"),
+                })
+                .collect()
+        };
+        let heterozygous = variants(&phenopackets[0]);
+        assert_eq!(1, heterozygous.len());
+        assert_eq!("NM_000138.5:c.123A>G", heterozygous[0].expressions[0].value);
+        assert_eq!("FBN1", heterozygous[0].gene_context.as_ref().unwrap().symbol);
+        assert_eq!(Some(AllelicState::heterozygous()), heterozygous[0].allelic_state);
+        let homozygous = variants(&phenopackets[1]);
+        assert_eq!(1, homozygous.len());
+        assert_eq!(Some(AllelicState::homozygous()), homozygous[0].allelic_state);
+        assert_eq!("PMID_12345_P_2", phenopackets[1].id);
+        assert!(phenopackets[1].phenotypic_features[0].excluded);
+    }
+
+    #[rstest]
+    fn test_missing_columns() {
+        let template = "PMID\ttitle\nCURIE\tstr\n";
+        let error = TemplateReader::new("me").read(template.as_bytes(), b'\t').unwrap_err();
+        assert!(error.to_string().starts_with("Template is missing the columns: individual_id, disease_id"));
+    }
+
+    #[rstest]
+    fn test_all_invalid_cells_are_reported() {
+        let header = HEADER.replace("HP:0001251", "HP:12");
+        let template = format!("{header}\
+            PMID:1\tt\tP1\t\tOMIM:620000\td\tna\tna\tna\tna\tna\tna\tP6Y3\tX\tna\tyes\tna\n\
+            PMID:1\tt\tP2\t\tOMIM:620000\td\tHGNC 3603\tna\tna\tna\tna\tna\tP6Y\tF\tna\tobserved\tna\n\
+            PMID:1\tt\tP3\t\tOMIM:620000\td\tna\tna\tna\tna\tna\tna\tP6Y\tF\tna\tobserved\tna\n");
+        let import = TemplateReader::new("me").read(template.as_bytes(), b'\t').unwrap();
+        assert_eq!(vec!["PMID_1_P3"], import.phenopackets.iter().map(|pp| pp.id.as_str()).collect::<Vec<_>>());
+        let locations: Vec<(usize, &str, &str)> = import.diagnostics.iter()
+            .map(|d| (d.row, d.column.as_str(), d.value.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (2, "Ataxia", "HP:12"),
+                (3, "sex", "X"),
+                (3, "age_at_last_encounter", "P6Y3"),
+                (3, "Seizure", "yes"),
+                (4, "HGNC_id", "HGNC 3603"),
+            ],
+            locations);
+        assert!(matches!(import.diagnostics[0].error, Error::CurieError { .. }));
+        assert!(matches!(import.diagnostics[2].error, Error::TimeElementError { .. }));
+        assert!(matches!(import.diagnostics[4].error, Error::CurieError { .. }));
+        assert_eq!(
+            "Row 3, column 'sex', value 'X': Unknown sex 'X' (expected M, F, O, or U)",
+            import.diagnostics[1].to_string());
+    }
+}
@@ -0,0 +1,147 @@
+//! Derivation of HPO features from numeric lab values
+//!
+//! A [`ThresholdMapper`] knows the assay, the unit, and the reference range of a lab test and the HPO terms for
+//! values below and above the range. A value is recorded as a [`Measurement`] together with the derived feature,
+//! e.g., a low serum calcium with HP:0002901 (Hypocalcemia). A value inside the range excludes the abnormalities.
+use phenopackets::schema::v2::core::{Measurement, OntologyClass, PhenotypicFeature};
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::builders::builder::Builder;
+use crate::builders::measurement_builder::MeasurementBuilder;
+use crate::error::Result;
+
+
+/// Maps the values of a lab test to a Measurement and HPO features.
+///
+/// ```
+/// use phenopacket_tools::builders::builder::Builder;
+/// use phenopacket_tools::constants::units::Unit;
+/// use phenopacket_tools::tabular::threshold::ThresholdMapper;
+///
+/// let calcium = Builder::ontology_class("LOINC:17861-6", "Calcium [Mass/volume] in Serum or Plasma").unwrap();
+/// let mapper = ThresholdMapper::new(calcium, Unit::milligram_per_deciliter(), 8.5, 10.5)
+///     .unwrap()
+///     .below(Builder::ontology_class("HP:0002901", "Hypocalcemia").unwrap())
+///     .above(Builder::ontology_class("HP:0003072", "Hypercalcemia").unwrap());
+/// let features = mapper.phenotypic_features(7.1);
+/// assert_eq!("Hypocalcemia", features[0].r#type.as_ref().unwrap().label);
+/// assert!(!features[0].excluded);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdMapper {
+    assay: OntologyClass,
+    unit: OntologyClass,
+    low: f64,
+    high: f64,
+    below: Option<OntologyClass>,
+    above: Option<OntologyClass>,
+}
+
+impl ThresholdMapper {
+    /// A mapper for the assay (a LOINC term) with values in `unit` and the normal range `low..=high`. Returns an
+    /// error if [`MeasurementBuilder`] rejects the reference range.
+    pub fn new(assay: OntologyClass, unit: OntologyClass, low: f64, high: f64) -> Result<Self> {
+        let mapper = ThresholdMapper { assay, unit, low, high, below: None, above: None };
+        mapper.measurement_builder(low).build()?;
+        Ok(mapper)
+    }
+
+    /// The HPO term for values below the reference range.
+    pub fn below(mut self, term: OntologyClass) -> Self {
+        self.below = Some(term);
+        self
+    }
+
+    /// The HPO term for values above the reference range.
+    pub fn above(mut self, term: OntologyClass) -> Self {
+        self.above = Some(term);
+        self
+    }
+
+    /// The Measurement of the value, with the reference range.
+    pub fn measurement(&self, number: f64) -> Measurement {
+        self.measurement_builder(number).build().expect("the reference range was checked in new")
+    }
+
+    fn measurement_builder(&self, number: f64) -> MeasurementBuilder {
+        MeasurementBuilder::new(self.assay.clone())
+            .quantity(number, self.unit.clone())
+            .reference_range(self.low, self.high)
+    }
+
+    /// The observed abnormality if the value is outside the reference range, or all abnormalities as excluded if
+    /// it is inside. There is no feature for a value outside the range if no term is set for that side.
+    pub fn phenotypic_features(&self, number: f64) -> Vec<PhenotypicFeature> {
+        if number < self.low {
+            self.below.iter().cloned().map(Builder::phenotypic_feature_observed).collect()
+        } else if number > self.high {
+            self.above.iter().cloned().map(Builder::phenotypic_feature_observed).collect()
+        } else {
+            self.below.iter().chain(&self.above).cloned().map(Builder::phenotypic_feature_excluded).collect()
+        }
+    }
+
+    /// Add the Measurement and the derived features to the phenopacket.
+    pub fn add_to(&self, phenopacket: &mut Phenopacket, number: f64) {
+        phenopacket.measurements.push(self.measurement(number));
+        phenopacket.phenotypic_features.extend(self.phenotypic_features(number));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::units::Unit;
+    use crate::error::Error;
+    use phenopackets::schema::v2::core::measurement::MeasurementValue;
+    use phenopackets::schema::v2::core::{value, Value};
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn calcium() -> ThresholdMapper {
+        let assay = Builder::ontology_class("LOINC:17861-6", "Calcium [Mass/volume] in Serum or Plasma").unwrap();
+        ThresholdMapper::new(assay, Unit::milligram_per_deciliter(), 8.5, 10.5)
+            .unwrap()
+            .below(Builder::ontology_class("HP:0002901", "Hypocalcemia").unwrap())
+            .above(Builder::ontology_class("HP:0003072", "Hypercalcemia").unwrap())
+    }
+
+    #[rstest]
+    #[case(7.1, vec![("HP:0002901", false)])]
+    #[case(8.5, vec![("HP:0002901", true), ("HP:0003072", true)])]
+    #[case(9.4, vec![("HP:0002901", true), ("HP:0003072", true)])]
+    #[case(11.0, vec![("HP:0003072", false)])]
+    fn test_phenotypic_features(calcium: ThresholdMapper, #[case] value: f64, #[case] expected: Vec<(&str, bool)>) {
+        let features: Vec<(String, bool)> = calcium
+            .phenotypic_features(value)
+            .into_iter()
+            .map(|f| (f.r#type.unwrap().id, f.excluded))
+            .collect();
+        let expected: Vec<(String, bool)> = expected.into_iter().map(|(id, excluded)| (id.to_string(), excluded)).collect();
+        assert_eq!(expected, features);
+    }
+
+    #[rstest]
+    fn test_add_to(calcium: ThresholdMapper) {
+        let mut pp = Builder::phenopacket("pp", Builder::meta_data_now("me"));
+        calcium.add_to(&mut pp, 7.1);
+        assert_eq!(1, pp.phenotypic_features.len());
+        let Some(MeasurementValue::Value(Value { value: Some(value::Value::Quantity(quantity)) })) =
+            &pp.measurements[0].measurement_value
+        else {
+            panic!("expected a quantity");
+        };
+        assert_eq!(7.1, quantity.value);
+        let range = quantity.reference_range.as_ref().unwrap();
+        assert_eq!((8.5, 10.5), (range.low, range.high));
+        assert_eq!(Some(Unit::milligram_per_deciliter()), range.unit);
+    }
+
+    #[rstest]
+    fn test_invalid_range() {
+        let assay = Builder::ontology_class("LOINC:17861-6", "Calcium").unwrap();
+        let result = ThresholdMapper::new(assay, Unit::milligram_per_deciliter(), 10.5, 8.5);
+        assert!(matches!(result, Err(Error::MeasurementError { .. })));
+    }
+}
@@ -0,0 +1,147 @@
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::validation::phenopacket_validator::PhenopacketValidator;
+use crate::validation::validation_result::ValidationResult;
+
+pub const MISSING_PHENOPACKET_ID: &str = "MISSING_PHENOPACKET_ID";
+pub const MISSING_META_DATA: &str = "MISSING_META_DATA";
+pub const MISSING_META_DATA_CREATED: &str = "MISSING_META_DATA_CREATED";
+pub const MISSING_META_DATA_CREATED_BY: &str = "MISSING_META_DATA_CREATED_BY";
+pub const MISSING_SCHEMA_VERSION: &str = "MISSING_SCHEMA_VERSION";
+pub const MISSING_PHENOTYPIC_FEATURE_TYPE: &str = "MISSING_PHENOTYPIC_FEATURE_TYPE";
+pub const MISSING_DISEASE_TERM: &str = "MISSING_DISEASE_TERM";
+pub const MISSING_INTERPRETATION_ID: &str = "MISSING_INTERPRETATION_ID";
+pub const MISSING_DIAGNOSIS_DISEASE: &str = "MISSING_DIAGNOSIS_DISEASE";
+pub const MISSING_INDIVIDUAL_ID: &str = "MISSING_INDIVIDUAL_ID";
+
+
+/// Check the fields that are required by the Phenopacket Schema v2.
+pub struct BaseValidator;
+
+impl PhenopacketValidator for BaseValidator {
+    fn id(&self) -> &str {
+        "base"
+    }
+
+    fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+        let mut results = vec![];
+        if phenopacket.id.trim().is_empty() {
+            results.push(ValidationResult::error("/id", MISSING_PHENOPACKET_ID, "Phenopacket id must not be empty"));
+        }
+        if let Some(subject) = &phenopacket.subject {
+            if subject.id.trim().is_empty() {
+                results.push(ValidationResult::error("/subject/id", MISSING_INDIVIDUAL_ID, "Individual id must not be empty"));
+            }
+        }
+        match &phenopacket.meta_data {
+            Some(meta_data) => {
+                if meta_data.created.is_none() {
+                    results.push(ValidationResult::error("/metaData/created", MISSING_META_DATA_CREATED, "MetaData must have a created timestamp"));
+                }
+                if meta_data.created_by.trim().is_empty() {
+                    results.push(ValidationResult::error("/metaData/createdBy", MISSING_META_DATA_CREATED_BY, "MetaData must have a createdBy field"));
+                }
+                if meta_data.phenopacket_schema_version.trim().is_empty() {
+                    results.push(ValidationResult::error("/metaData/phenopacketSchemaVersion", MISSING_SCHEMA_VERSION, "MetaData must have a phenopacketSchemaVersion"));
+                }
+            }
+            None => {
+                results.push(ValidationResult::error("/metaData", MISSING_META_DATA, "Phenopacket must have MetaData"));
+            }
+        }
+        for (i, feature) in phenopacket.phenotypic_features.iter().enumerate() {
+            if feature.r#type.is_none() {
+                results.push(ValidationResult::error(
+                    format!("/phenotypicFeatures/{i}/type"),
+                    MISSING_PHENOTYPIC_FEATURE_TYPE,
+                    "PhenotypicFeature must have a type"));
+            }
+        }
+        for (i, disease) in phenopacket.diseases.iter().enumerate() {
+            if disease.term.is_none() {
+                results.push(ValidationResult::error(
+                    format!("/diseases/{i}/term"),
+                    MISSING_DISEASE_TERM,
+                    "Disease must have a term"));
+            }
+        }
+        for (i, interpretation) in phenopacket.interpretations.iter().enumerate() {
+            if interpretation.id.trim().is_empty() {
+                results.push(ValidationResult::error(
+                    format!("/interpretations/{i}/id"),
+                    MISSING_INTERPRETATION_ID,
+                    "Interpretation id must not be empty"));
+            }
+            let has_disease = interpretation.diagnosis.as_ref().is_some_and(|d| d.disease.is_some());
+            if !has_disease {
+                results.push(ValidationResult::error(
+                    format!("/interpretations/{i}/diagnosis/disease"),
+                    MISSING_DIAGNOSIS_DISEASE,
+                    "Interpretation must have a diagnosis with a disease"));
+            }
+        }
+        results
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets;
+    use crate::builders::phenopacket_builder::PhenopacketBuilder;
+    use phenopackets::schema::v2::core::{Diagnosis, Disease, Interpretation, PhenotypicFeature};
+    use rstest::rstest;
+
+    fn rule_ids(results: &[ValidationResult]) -> Vec<&str> {
+        results.iter().map(|r| r.rule_id.as_str()).collect()
+    }
+
+    #[rstest]
+    fn test_valid_phenopacket() {
+        let vsd = Builder::ontology_class("HP:0001629", "Ventricular septal defect").unwrap();
+        let phenopacket = PhenopacketBuilder::new("id")
+            .subject(Builder::individual("proband A"))
+            .meta_data(Builder::meta_data_now("anonymous biocurator"))
+            .phenotypic_feature(Builder::phenotypic_feature_observed(vsd))
+            .build()
+            .unwrap();
+        assert!(BaseValidator.validate(&phenopacket).is_empty());
+    }
+
+    #[rstest]
+    fn test_unfilled_phenopacket() {
+        let mut meta_data = Builder::meta_data_now("");
+        meta_data.created = None;
+        let phenopacket = Builder::phenopacket("", meta_data);
+        let results = BaseValidator.validate(&phenopacket);
+        assert_eq!(vec![MISSING_PHENOPACKET_ID, MISSING_META_DATA_CREATED, MISSING_META_DATA_CREATED_BY], rule_ids(&results));
+    }
+
+    #[rstest]
+    fn test_missing_meta_data() {
+        let phenopacket = Phenopacket { id: "id".to_string(), ..Default::default() };
+        let results = BaseValidator.validate(&phenopacket);
+        assert_eq!(vec![MISSING_META_DATA], rule_ids(&results));
+        assert_eq!("/metaData", results[0].path);
+    }
+
+    #[rstest]
+    fn test_missing_nested_fields() {
+        let mut phenopacket = test_phenopackets::phenopacket();
+        phenopacket.subject = Some(Builder::individual(""));
+        phenopacket.phenotypic_features.push(PhenotypicFeature::default());
+        phenopacket.diseases.push(Disease::default());
+        phenopacket.interpretations.push(Interpretation { diagnosis: Some(Diagnosis::default()), ..Default::default() });
+        let results = BaseValidator.validate(&phenopacket);
+        assert_eq!(vec![
+            MISSING_INDIVIDUAL_ID,
+            MISSING_PHENOTYPIC_FEATURE_TYPE,
+            MISSING_DISEASE_TERM,
+            MISSING_INTERPRETATION_ID,
+            MISSING_DIAGNOSIS_DISEASE], rule_ids(&results));
+        assert_eq!("/phenotypicFeatures/0/type", results[1].path);
+        assert_eq!("/interpretations/0/diagnosis/disease", results[4].path);
+    }
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::ontology::ontology_graph::Ontology;
+use crate::validation::phenopacket_validator::PhenopacketValidator;
+use crate::validation::validation_result::ValidationResult;
+
+pub const REDUNDANT_OBSERVED_ANCESTOR: &str = "REDUNDANT_OBSERVED_ANCESTOR";
+pub const REDUNDANT_EXCLUDED_DESCENDANT: &str = "REDUNDANT_EXCLUDED_DESCENDANT";
+pub const OBSERVED_WITH_EXCLUDED_ANCESTOR: &str = "OBSERVED_WITH_EXCLUDED_ANCESTOR";
+pub const OBSERVED_AND_EXCLUDED: &str = "OBSERVED_AND_EXCLUDED";
+
+
+/// Check the phenotypic features of a phenopacket against the is_a hierarchy of the ontology (e.g., the HPO).
+///
+/// * an observed term together with an observed ancestor is redundant (the ancestor is implied)
+/// * an excluded term together with an excluded descendant is redundant (the descendant is implied)
+/// * an observed term together with an excluded ancestor is a logical contradiction, as is a term that is both
+///   observed and excluded
+pub struct HierarchyValidator {
+    ontology: Arc<Ontology>,
+}
+
+impl HierarchyValidator {
+    pub fn new(ontology: Arc<Ontology>) -> Self {
+        HierarchyValidator { ontology }
+    }
+}
+
+impl PhenopacketValidator for HierarchyValidator {
+    fn id(&self) -> &str {
+        "hierarchy"
+    }
+
+    fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+        let mut results = vec![];
+        // index, primary id, label, excluded
+        let features: Vec<(usize, &str, &str, bool)> = phenopacket
+            .phenotypic_features
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pf)| {
+                let clz = pf.r#type.as_ref()?;
+                let id = self.ontology.primary_id(&clz.id)?;
+                Some((i, id, clz.label.as_str(), pf.excluded))
+            })
+            .collect();
+        for &(i, id, label, excluded) in &features {
+            let ancestors = self.ontology.ancestors(id);
+            for &(j, other_id, other_label, other_excluded) in &features {
+                if other_id == id {
+                    if !excluded && other_excluded {
+                        results.push(ValidationResult::error(
+                            format!("/phenotypicFeatures/{i}"),
+                            OBSERVED_AND_EXCLUDED,
+                            format!("{id} ({label}) is observed but also excluded at /phenotypicFeatures/{j}")));
+                    }
+                    continue;
+                }
+                if !ancestors.contains(other_id) {
+                    continue;
+                }
+                match (excluded, other_excluded) {
+                    (false, false) => results.push(ValidationResult::warning(
+                        format!("/phenotypicFeatures/{j}"),
+                        REDUNDANT_OBSERVED_ANCESTOR,
+                        format!("{other_id} ({other_label}) is redundant because its descendant {id} ({label}) is observed"))),
+                    (true, true) => results.push(ValidationResult::warning(
+                        format!("/phenotypicFeatures/{i}"),
+                        REDUNDANT_EXCLUDED_DESCENDANT,
+                        format!("{id} ({label}) is redundant because its ancestor {other_id} ({other_label}) is excluded"))),
+                    (false, true) => results.push(ValidationResult::error(
+                        format!("/phenotypicFeatures/{i}"),
+                        OBSERVED_WITH_EXCLUDED_ANCESTOR,
+                        format!("{id} ({label}) is observed but its ancestor {other_id} ({other_label}) is excluded"))),
+                    (true, false) => {} // excluding a specific term below an observed one is fine
+                }
+            }
+        }
+        results
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets;
+    use crate::ontology::test_ontology::toy_hpo;
+    use crate::validation::validation_result::Severity;
+    use rstest::rstest;
+
+    fn phenopacket_with(features: &[(&str, &str, bool)]) -> Phenopacket {
+        let mut pp = test_phenopackets::phenopacket();
+        for (id, label, excluded) in features {
+            let clz = Builder::ontology_class(*id, *label).unwrap();
+            let pf = if *excluded {
+                Builder::phenotypic_feature_excluded(clz)
+            } else {
+                Builder::phenotypic_feature_observed(clz)
+            };
+            pp.phenotypic_features.push(pf);
+        }
+        pp
+    }
+
+    fn rule_ids(pp: &Phenopacket) -> Vec<String> {
+        HierarchyValidator::new(Arc::new(toy_hpo()))
+            .validate(pp)
+            .into_iter()
+            .map(|r| r.rule_id)
+            .collect()
+    }
+
+    #[rstest]
+    fn test_mixed_features() {
+        let pp = phenopacket_with(&[
+            ("HP:0001629", "Ventricular septal defect", false),
+            ("HP:0000518", "Cataract", true),
+            ("HP:0001627", "Abnormal heart morphology", false),
+        ]);
+        // VSD is a descendant of Abnormal heart morphology
+        assert_eq!(vec![REDUNDANT_OBSERVED_ANCESTOR], rule_ids(&pp));
+        let pp = phenopacket_with(&[
+            ("HP:0001627", "Abnormal heart morphology", false),
+            ("HP:0001629", "Ventricular septal defect", true),
+            ("HP:0000518", "Cataract", true),
+        ]);
+        assert!(rule_ids(&pp).is_empty());
+    }
+
+    #[rstest]
+    fn test_redundant_observed_ancestor() {
+        let pp = phenopacket_with(&[
+            ("HP:0001629", "Ventricular septal defect", false),
+            ("HP:0001626", "Abnormality of the cardiovascular system", false),
+        ]);
+        let results = HierarchyValidator::new(Arc::new(toy_hpo())).validate(&pp);
+        assert_eq!(1, results.len());
+        assert_eq!(Severity::Warning, results[0].severity);
+        assert_eq!("/phenotypicFeatures/1", results[0].path);
+        assert_eq!(
+            "HP:0001626 (Abnormality of the cardiovascular system) is redundant because its descendant HP:0001629 (Ventricular septal defect) is observed",
+            results[0].message);
+    }
+
+    #[rstest]
+    fn test_redundant_excluded_descendant() {
+        let pp = phenopacket_with(&[
+            ("HP:0001629", "Ventricular septal defect", true),
+            ("HP:0001627", "Abnormal heart morphology", true),
+        ]);
+        assert_eq!(vec![REDUNDANT_EXCLUDED_DESCENDANT], rule_ids(&pp));
+    }
+
+    // HP:0001630 is an alternate id of VSD
+    #[rstest]
+    #[case("HP:0001627", "Abnormal heart morphology", OBSERVED_WITH_EXCLUDED_ANCESTOR)]
+    #[case("HP:0001629", "Ventricular septal defect", OBSERVED_AND_EXCLUDED)]
+    fn test_observed_with_excluded_ancestor(
+        #[case] excluded_id: &str,
+        #[case] excluded_label: &str,
+        #[case] rule_id: &str,
+    ) {
+        let pp = phenopacket_with(&[
+            (excluded_id, excluded_label, true),
+            ("HP:0001630", "Ventricular septal defect", false),
+        ]);
+        let results = HierarchyValidator::new(Arc::new(toy_hpo())).validate(&pp);
+        assert_eq!(1, results.len());
+        assert_eq!(rule_id, results[0].rule_id);
+        assert_eq!(Severity::Error, results[0].severity);
+        assert_eq!("/phenotypicFeatures/1", results[0].path);
+    }
+}
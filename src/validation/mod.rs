@@ -0,0 +1,13 @@
+//! Q/C of GA4GH phenopackets
+//!
+//! Validators implement [`phenopacket_validator::PhenopacketValidator`] and report every problem they find
+//! as a [`validation_result::ValidationResult`]. The [`phenopacket_validator::ValidationRunner`] applies a
+//! configurable set of validators to a phenopacket and collects all issues in a single pass.
+pub mod base_validator;
+pub mod hierarchy_validator;
+pub mod obsolete_term_fixer;
+pub mod obsolete_term_validator;
+pub mod phenopacket_validator;
+pub mod phenotypic_abnormality_validator;
+pub mod resource_validator;
+pub mod validation_result;
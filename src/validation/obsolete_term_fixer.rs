@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use phenopackets::schema::v2::core::OntologyClass;
+use phenopackets::schema::v2::Phenopacket;
+use serde::{Deserialize, Serialize};
+
+use crate::ontology::ontology_graph::Ontology;
+use crate::ontology_class_visitor::visit_mut;
+
+
+/// Record of one OntologyClass that was rewritten by the [`ObsoleteTermFixer`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermReplacement {
+    /// JSON pointer of the OntologyClass in the phenopacket
+    pub path: String,
+    pub original_id: String,
+    pub original_label: String,
+    pub replacement_id: String,
+    pub replacement_label: String,
+}
+
+
+/// Rewrite obsolete and alternate ids to the current primary id and label of the ontology.
+///
+/// Obsolete terms without a `replaced_by` link cannot be fixed automatically and are left unchanged.
+pub struct ObsoleteTermFixer {
+    ontology: Arc<Ontology>,
+}
+
+impl ObsoleteTermFixer {
+    pub fn new(ontology: Arc<Ontology>) -> Self {
+        ObsoleteTermFixer { ontology }
+    }
+
+    /// Fix the phenopacket in place and return a record of every change.
+    pub fn fix(&self, phenopacket: &mut Phenopacket) -> Vec<TermReplacement> {
+        let mut replacements = vec![];
+        visit_mut::phenopacket(phenopacket, &mut |path, clz: &mut OntologyClass| {
+            let outdated = self.ontology.is_alternate_id(&clz.id)
+                || self.ontology.term(&clz.id).is_some_and(|t| t.obsolete);
+            if !outdated {
+                return;
+            }
+            if let Some(term) = self.ontology.current_term(&clz.id) {
+                replacements.push(TermReplacement {
+                    path: path.to_string(),
+                    original_id: clz.id.clone(),
+                    original_label: clz.label.clone(),
+                    replacement_id: term.id.clone(),
+                    replacement_label: term.label.clone(),
+                });
+                clz.id = term.id.clone();
+                clz.label = term.label.clone();
+            }
+        });
+        replacements
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets;
+    use crate::ontology::test_ontology::toy_hpo;
+    use crate::validation::obsolete_term_validator::ObsoleteTermValidator;
+    use crate::validation::phenopacket_validator::PhenopacketValidator;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_fix() {
+        let hpo = Arc::new(toy_hpo());
+        let mut pp = test_phenopackets::phenopacket();
+        for (id, label) in [("HP:0001630", "VSD"), ("HP:0006000", "Ventricular hole"), ("HP:0006001", "Abnormal septum"), ("HP:0000518", "Cataract")] {
+            let clz = Builder::ontology_class(id, label).unwrap();
+            pp.phenotypic_features.push(Builder::phenotypic_feature_observed(clz));
+        }
+        let replacements = ObsoleteTermFixer::new(hpo.clone()).fix(&mut pp);
+        assert_eq!(2, replacements.len());
+        assert_eq!(TermReplacement {
+            path: "/phenotypicFeatures/1/type".to_string(),
+            original_id: "HP:0006000".to_string(),
+            original_label: "Ventricular hole".to_string(),
+            replacement_id: "HP:0001629".to_string(),
+            replacement_label: "Ventricular septal defect".to_string(),
+        }, replacements[1]);
+        let vsd = pp.phenotypic_features[0].r#type.as_ref().unwrap();
+        assert_eq!("HP:0001629", vsd.id);
+        assert_eq!("Ventricular septal defect", vsd.label);
+        // only the obsolete term without replacement remains
+        let results = ObsoleteTermValidator::new(hpo).validate(&pp);
+        assert_eq!(1, results.len());
+        assert_eq!("/phenotypicFeatures/2/type", results[0].path);
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::ontology::ontology_graph::Ontology;
+use crate::ontology_class_visitor::ontology_classes;
+use crate::validation::phenopacket_validator::PhenopacketValidator;
+use crate::validation::validation_result::ValidationResult;
+
+pub const OBSOLETE_TERM_ID: &str = "OBSOLETE_TERM_ID";
+pub const ALTERNATE_TERM_ID: &str = "ALTERNATE_TERM_ID";
+
+
+/// Flag OntologyClass messages whose id is obsolete (error) or an alternate id (warning) in the given ontology.
+///
+/// Ids that are not in the ontology (e.g., OMIM ids when checking against the HPO) are ignored.
+/// See [`crate::validation::obsolete_term_fixer::ObsoleteTermFixer`] to replace such ids.
+pub struct ObsoleteTermValidator {
+    ontology: Arc<Ontology>,
+}
+
+impl ObsoleteTermValidator {
+    pub fn new(ontology: Arc<Ontology>) -> Self {
+        ObsoleteTermValidator { ontology }
+    }
+}
+
+impl PhenopacketValidator for ObsoleteTermValidator {
+    fn id(&self) -> &str {
+        "obsolete-terms"
+    }
+
+    fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+        let mut results = vec![];
+        for (path, clz) in ontology_classes(phenopacket) {
+            if self.ontology.is_alternate_id(&clz.id) {
+                let msg = match self.ontology.current_term(&clz.id) {
+                    Some(term) => format!("{} is an alternate id; use {} ({})", clz.id, term.id, term.label),
+                    None => format!("{} is an alternate id", clz.id),
+                };
+                results.push(ValidationResult::warning(path, ALTERNATE_TERM_ID, msg));
+            } else if let Some(term) = self.ontology.term(&clz.id).filter(|t| t.obsolete) {
+                let msg = match self.ontology.current_term(&clz.id) {
+                    Some(replacement) => format!("{} is obsolete; replace with {} ({})", clz.id, replacement.id, replacement.label),
+                    None if !term.consider.is_empty() => format!("{} is obsolete; consider {}", clz.id, term.consider.join(", ")),
+                    None => format!("{} is obsolete", clz.id),
+                };
+                results.push(ValidationResult::error(path, OBSOLETE_TERM_ID, msg));
+            }
+        }
+        results
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets;
+    use crate::ontology::test_ontology::toy_hpo;
+    use crate::validation::validation_result::Severity;
+    use rstest::rstest;
+
+    fn phenopacket_with(ids: &[&str]) -> Phenopacket {
+        let mut pp = test_phenopackets::phenopacket();
+        for id in ids {
+            let clz = Builder::ontology_class(*id, "some label").unwrap();
+            pp.phenotypic_features.push(Builder::phenotypic_feature_observed(clz));
+        }
+        pp
+    }
+
+    #[rstest]
+    fn test_current_terms() {
+        let validator = ObsoleteTermValidator::new(Arc::new(toy_hpo()));
+        let pp = phenopacket_with(&["HP:0001629", "HP:0000518", "OMIM:158810"]);
+        assert!(validator.validate(&pp).is_empty());
+    }
+
+    #[rstest]
+    fn test_outdated_terms() {
+        let validator = ObsoleteTermValidator::new(Arc::new(toy_hpo()));
+        let pp = phenopacket_with(&["HP:0001630", "HP:0006000", "HP:0006001"]);
+        let results = validator.validate(&pp);
+        assert_eq!(3, results.len());
+        assert_eq!(ALTERNATE_TERM_ID, results[0].rule_id);
+        assert_eq!(Severity::Warning, results[0].severity);
+        assert_eq!("HP:0001630 is an alternate id; use HP:0001629 (Ventricular septal defect)", results[0].message);
+        assert_eq!(OBSOLETE_TERM_ID, results[1].rule_id);
+        assert_eq!("/phenotypicFeatures/1/type", results[1].path);
+        assert_eq!("HP:0006000 is obsolete; replace with HP:0001629 (Ventricular septal defect)", results[1].message);
+        assert_eq!("HP:0006001 is obsolete; consider HP:0010438", results[2].message);
+    }
+}
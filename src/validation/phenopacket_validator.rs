@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::validation::validation_result::{Severity, ValidationResult};
+
+
+/// A check that is applied to a complete phenopacket.
+///
+/// Implementations must report every issue they find rather than stopping at the first one.
+/// Validators are `Send + Sync` so that one runner can be shared between threads.
+pub trait PhenopacketValidator: Send + Sync {
+    /// A short, stable identifier of the validator (e.g., "base").
+    fn id(&self) -> &str;
+
+    /// Check the phenopacket and return all issues (an empty vector if there are none).
+    fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult>;
+}
+
+
+/// Apply a configurable set of validators to phenopackets and collect all issues.
+#[derive(Default)]
+pub struct ValidationRunner {
+    validators: Vec<Box<dyn PhenopacketValidator>>,
+    ignored_rules: HashSet<String>,
+}
+
+impl ValidationRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a validator (builder style).
+    pub fn with_validator(mut self, validator: impl PhenopacketValidator + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    pub fn add_validator(&mut self, validator: Box<dyn PhenopacketValidator>) {
+        self.validators.push(validator);
+    }
+
+    /// Suppress all issues with the given rule id.
+    pub fn ignore_rule(mut self, rule_id: impl Into<String>) -> Self {
+        self.ignored_rules.insert(rule_id.into());
+        self
+    }
+
+    pub fn validator_ids(&self) -> Vec<&str> {
+        self.validators.iter().map(|v| v.id()).collect()
+    }
+
+    /// Run all validators on the phenopacket. Issues are returned in the order of the validators.
+    pub fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+        self.validators
+            .iter()
+            .flat_map(|v| v.validate(phenopacket))
+            .filter(|r| !self.ignored_rules.contains(&r.rule_id))
+            .collect()
+    }
+
+    /// Run all validators and return true if none of them reported an error.
+    pub fn is_valid(&self, phenopacket: &Phenopacket) -> bool {
+        self.validate(phenopacket)
+            .iter()
+            .all(|r| r.severity != Severity::Error)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    struct EmptyIdValidator;
+
+    impl PhenopacketValidator for EmptyIdValidator {
+        fn id(&self) -> &str {
+            "empty-id"
+        }
+
+        fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+            let mut results = vec![];
+            if phenopacket.id.is_empty() {
+                results.push(ValidationResult::error("/id", "EMPTY_ID", "Phenopacket id is empty"));
+            }
+            if phenopacket.subject.is_none() {
+                results.push(ValidationResult::warning("/subject", "NO_SUBJECT", "Phenopacket has no subject"));
+            }
+            results
+        }
+    }
+
+    #[rstest]
+    fn test_runner_collects_all_issues() {
+        let runner = ValidationRunner::new().with_validator(EmptyIdValidator);
+        let results = runner.validate(&Phenopacket::default());
+        assert_eq!(2, results.len());
+        assert_eq!("/id", results[0].path);
+        assert_eq!("/subject", results[1].path);
+        assert!(!runner.is_valid(&Phenopacket::default()));
+        assert_eq!(vec!["empty-id"], runner.validator_ids());
+    }
+
+    #[rstest]
+    fn test_ignore_rule() {
+        let runner = ValidationRunner::new()
+            .with_validator(EmptyIdValidator)
+            .ignore_rule("EMPTY_ID");
+        let results = runner.validate(&Phenopacket::default());
+        assert_eq!(1, results.len());
+        assert_eq!("NO_SUBJECT", results[0].rule_id);
+        assert!(runner.is_valid(&Phenopacket::default()));
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::ontology::ontology_graph::Ontology;
+use crate::validation::phenopacket_validator::PhenopacketValidator;
+use crate::validation::validation_result::ValidationResult;
+
+pub const NOT_A_PHENOTYPIC_ABNORMALITY: &str = "NOT_A_PHENOTYPIC_ABNORMALITY";
+
+pub const PHENOTYPIC_ABNORMALITY: &str = "HP:0000118";
+pub const MODE_OF_INHERITANCE: &str = "HP:0000005";
+pub const CLINICAL_MODIFIER: &str = "HP:0012823";
+pub const CLINICAL_COURSE: &str = "HP:0031797";
+pub const FREQUENCY: &str = "HP:0040279";
+
+/// HPO subontologies whose terms are frequently misused as phenotypic features.
+const MISUSED_SUBONTOLOGIES: [(&str, &str); 4] = [
+    (MODE_OF_INHERITANCE, "Mode of inheritance"),
+    (CLINICAL_MODIFIER, "Clinical modifier"),
+    (CLINICAL_COURSE, "Clinical course"),
+    (FREQUENCY, "Frequency"),
+];
+
+
+/// Require the type of each PhenotypicFeature to be a descendant of Phenotypic abnormality (HP:0000118).
+///
+/// Terms from other HPO subontologies, e.g., onset terms such as Congenital onset, are reported as errors
+/// together with the subontology they belong to. Ids that are not in the ontology are ignored.
+pub struct PhenotypicAbnormalityValidator {
+    ontology: Arc<Ontology>,
+}
+
+impl PhenotypicAbnormalityValidator {
+    pub fn new(ontology: Arc<Ontology>) -> Self {
+        PhenotypicAbnormalityValidator { ontology }
+    }
+}
+
+impl PhenopacketValidator for PhenotypicAbnormalityValidator {
+    fn id(&self) -> &str {
+        "phenotypic-abnormality"
+    }
+
+    fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+        let mut results = vec![];
+        for (i, pf) in phenopacket.phenotypic_features.iter().enumerate() {
+            let Some(clz) = pf.r#type.as_ref() else {
+                continue;
+            };
+            let Some(id) = self.ontology.primary_id(&clz.id) else {
+                continue;
+            };
+            let ancestors = self.ontology.ancestors(id);
+            if id == PHENOTYPIC_ABNORMALITY || ancestors.contains(PHENOTYPIC_ABNORMALITY) {
+                continue;
+            }
+            let subontology = MISUSED_SUBONTOLOGIES
+                .iter()
+                .find(|(root, _)| id == *root || ancestors.contains(root))
+                .map(|(_, name)| *name);
+            let msg = match subontology {
+                Some(name) => format!("{} ({}) is a {name} term and not a Phenotypic abnormality", clz.id, clz.label),
+                None => format!("{} ({}) is not a Phenotypic abnormality", clz.id, clz.label),
+            };
+            results.push(ValidationResult::error(
+                format!("/phenotypicFeatures/{i}/type"),
+                NOT_A_PHENOTYPIC_ABNORMALITY,
+                msg));
+        }
+        results
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::test_phenopackets;
+    use crate::constants::onset::CONGENITAL_ONSET;
+    use crate::ontology::test_ontology::toy_hpo;
+    use rstest::rstest;
+
+    fn validate(id: &str, label: &str) -> Vec<ValidationResult> {
+        let mut pp = test_phenopackets::phenopacket();
+        let clz = Builder::ontology_class(id, label).unwrap();
+        pp.phenotypic_features.push(Builder::phenotypic_feature_observed(clz));
+        PhenotypicAbnormalityValidator::new(Arc::new(toy_hpo())).validate(&pp)
+    }
+
+    #[rstest]
+    #[case("HP:0001629", "Ventricular septal defect")]
+    #[case("HP:0000118", "Phenotypic abnormality")]
+    #[case("OMIM:158810", "Bethlem myopathy 1")]
+    fn test_valid_feature_types(#[case] id: &str, #[case] label: &str) {
+        assert!(validate(id, label).is_empty());
+    }
+
+    #[rstest]
+    #[case("HP:0000006", "Autosomal dominant inheritance", "Mode of inheritance")]
+    #[case("HP:0012832", "Bilateral", "Clinical modifier")]
+    #[case("HP:0040283", "Occasional", "Frequency")]
+    fn test_misused_subontologies(#[case] id: &str, #[case] label: &str, #[case] subontology: &str) {
+        let results = validate(id, label);
+        assert_eq!(1, results.len());
+        assert_eq!(NOT_A_PHENOTYPIC_ABNORMALITY, results[0].rule_id);
+        assert_eq!(format!("{id} ({label}) is a {subontology} term and not a Phenotypic abnormality"), results[0].message);
+    }
+
+    #[rstest]
+    fn test_onset_as_feature_type() {
+        let results = validate(&CONGENITAL_ONSET.id, &CONGENITAL_ONSET.label);
+        assert_eq!(1, results.len());
+        assert_eq!("/phenotypicFeatures/0/type", results[0].path);
+        assert_eq!("HP:0003577 (Congenital onset) is a Clinical course term and not a Phenotypic abnormality", results[0].message);
+    }
+}
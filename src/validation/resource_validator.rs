@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashSet};
+
+use phenopackets::schema::v2::Phenopacket;
+
+use crate::builders::builder::curie_prefix;
+use crate::ontology_class_visitor::ontology_classes;
+use crate::validation::phenopacket_validator::PhenopacketValidator;
+use crate::validation::validation_result::ValidationResult;
+
+pub const MISSING_RESOURCE: &str = "MISSING_RESOURCE";
+pub const UNUSED_RESOURCE: &str = "UNUSED_RESOURCE";
+
+
+/// Check that every CURIE prefix used by an OntologyClass in the phenopacket is declared in `MetaData.resources`.
+///
+/// Prefixes are compared case-insensitively, as in [`crate::builders::resource_registry::ResourceRegistry`].
+/// A missing resource is reported once per prefix as an error (the path points to the first usage).
+/// Declared resources whose prefix is never used are reported as warnings.
+pub struct ResourceValidator;
+
+impl PhenopacketValidator for ResourceValidator {
+    fn id(&self) -> &str {
+        "resources"
+    }
+
+    fn validate(&self, phenopacket: &Phenopacket) -> Vec<ValidationResult> {
+        let mut results = vec![];
+        // lowercase prefix -> prefix and JSON pointer of the first OntologyClass that uses it
+        let mut used_prefixes: BTreeMap<String, (&str, String)> = BTreeMap::new();
+        for (path, clz) in ontology_classes(phenopacket) {
+            if let Some(prefix) = curie_prefix(&clz.id) {
+                used_prefixes.entry(prefix.to_lowercase()).or_insert((prefix, path));
+            }
+        }
+        let resources = phenopacket
+            .meta_data
+            .as_ref()
+            .map(|md| md.resources.as_slice())
+            .unwrap_or_default();
+        let declared: HashSet<String> = resources.iter().map(|r| r.namespace_prefix.to_lowercase()).collect();
+        for (key, (prefix, path)) in &used_prefixes {
+            if !declared.contains(key) {
+                results.push(ValidationResult::error(
+                    path.clone(),
+                    MISSING_RESOURCE,
+                    format!("No resource declared in MetaData for prefix '{prefix}'")));
+            }
+        }
+        for (i, resource) in resources.iter().enumerate() {
+            if !used_prefixes.contains_key(&resource.namespace_prefix.to_lowercase()) {
+                results.push(ValidationResult::warning(
+                    format!("/metaData/resources/{i}"),
+                    UNUSED_RESOURCE,
+                    format!("Resource '{}' is declared in MetaData but not used", resource.namespace_prefix)));
+            }
+        }
+        results
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::builder::Builder;
+    use crate::builders::resources::Resources;
+    use crate::builders::test_phenopackets::{self, bethlem_myopathy, vsd};
+    use crate::validation::validation_result::Severity;
+    use phenopackets::schema::v2::core::{OntologyClass, Resource};
+    use rstest::rstest;
+
+    fn phenopacket() -> Phenopacket {
+        let mut pp = test_phenopackets::phenopacket();
+        pp.phenotypic_features.push(Builder::phenotypic_feature_observed(vsd()));
+        pp.diseases.push(Builder::disease(bethlem_myopathy()));
+        pp
+    }
+
+    #[rstest]
+    fn test_all_resources_declared() {
+        let mut pp = phenopacket();
+        let md = pp.meta_data.as_mut().unwrap();
+        md.resources.push(Resources::hpo_version("2025-03-03"));
+        md.resources.push(Resources::omim_version("2022-11-23"));
+        assert!(ResourceValidator.validate(&pp).is_empty());
+    }
+
+    #[rstest]
+    fn test_prefixes_ignore_case() {
+        let mut pp = phenopacket();
+        let asd = OntologyClass { id: "hp:0001631".to_string(), label: "Atrial septal defect".to_string() };
+        pp.phenotypic_features.push(Builder::phenotypic_feature_observed(asd));
+        let md = pp.meta_data.as_mut().unwrap();
+        md.resources.push(Resources::hpo_version("2025-03-03"));
+        md.resources.push(Resource { namespace_prefix: "omim".to_string(), ..Resources::omim_version("2022-11-23") });
+        assert!(ResourceValidator.validate(&pp).is_empty());
+    }
+
+    #[rstest]
+    fn test_missing_resource() {
+        let mut pp = phenopacket();
+        pp.meta_data.as_mut().unwrap().resources.push(Resources::omim_version("2022-11-23"));
+        let results = ResourceValidator.validate(&pp);
+        assert_eq!(1, results.len());
+        assert_eq!(MISSING_RESOURCE, results[0].rule_id);
+        assert_eq!(Severity::Error, results[0].severity);
+        assert_eq!("/phenotypicFeatures/0/type", results[0].path);
+    }
+
+    #[rstest]
+    fn test_unused_resource() {
+        let mut pp = phenopacket();
+        let md = pp.meta_data.as_mut().unwrap();
+        md.resources.push(Resources::hpo_version("2025-03-03"));
+        md.resources.push(Resources::omim_version("2022-11-23"));
+        md.resources.push(Resources::geno_version("2023-10-08"));
+        let results = ResourceValidator.validate(&pp);
+        assert_eq!(1, results.len());
+        assert_eq!(UNUSED_RESOURCE, results[0].rule_id);
+        assert_eq!(Severity::Warning, results[0].severity);
+        assert_eq!("/metaData/resources/2", results[0].path);
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+
+/// How serious a validation issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// The phenopacket violates the Phenopacket Schema or a Q/C rule and should not be used as is.
+    Error,
+    /// The phenopacket is usable but probably contains a curation mistake.
+    Warning,
+    /// Informational message that does not require any action.
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "ERROR"),
+            Severity::Warning => write!(f, "WARNING"),
+            Severity::Info => write!(f, "INFO"),
+        }
+    }
+}
+
+
+/// A single issue found by a validator.
+///
+/// The `path` is a JSON pointer into the canonical JSON representation of the phenopacket
+/// (e.g., `/phenotypicFeatures/2/type`) and the `rule_id` is a stable identifier of the check
+/// that failed, which can be used to filter or suppress issues.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationResult {
+    pub severity: Severity,
+    pub path: String,
+    pub rule_id: String,
+    pub message: String,
+}
+
+impl ValidationResult {
+    pub fn new(
+        severity: Severity,
+        path: impl Into<String>,
+        rule_id: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        ValidationResult {
+            severity,
+            path: path.into(),
+            rule_id: rule_id.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn error(path: impl Into<String>, rule_id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, path, rule_id, message)
+    }
+
+    pub fn warning(path: impl Into<String>, rule_id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, path, rule_id, message)
+    }
+
+    pub fn info(path: impl Into<String>, rule_id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Info, path, rule_id, message)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ValidationResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {} ({}): {}", self.severity, self.path, self.rule_id, self.message)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_display() {
+        let result = ValidationResult::error("/phenotypicFeatures/0/type", "PHENOTYPIC_FEATURE_TYPE", "Missing type");
+        assert!(result.is_error());
+        assert_eq!("[ERROR] /phenotypicFeatures/0/type (PHENOTYPIC_FEATURE_TYPE): Missing type", result.to_string());
+    }
+
+    #[rstest]
+    fn test_severity_ordering() {
+        assert!(Severity::Error < Severity::Warning);
+        assert!(Severity::Warning < Severity::Info);
+    }
+}